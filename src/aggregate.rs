@@ -0,0 +1,313 @@
+//! Compact, mergeable survey summaries for distributed scoring.
+//!
+//! An [`NpsAggregate`] holds only per-rating counts, optionally per-attribute counts, and the
+//! earliest and latest response times. Merging two aggregates adds their counts, so partial
+//! results from shards, threads or map-reduce jobs combine exactly and in any grouping or order.
+//! With the `serde` feature, aggregates can be serialized and sent between workers.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//!
+//! let mut shard_a = Survey::new();
+//! shard_a.add_response_with_attributes(1, 10, [("region", "EMEA")]).unwrap();
+//! let mut shard_b = Survey::new();
+//! shard_b.add_response_with_attributes(2, 3, [("region", "APAC")]).unwrap();
+//! shard_b.add_response_with_attributes(3, 9, [("region", "EMEA")]).unwrap();
+//!
+//! let mut total = shard_a.aggregate(&["region"]);
+//! total.merge(&shard_b.aggregate(&["region"]));
+//!
+//! assert_eq!(total.total(), 3);
+//! assert!((total.score_exact() - 100.0 / 3.0).abs() < 1e-9);
+//! assert_eq!(total.group("region").unwrap()["EMEA"].total(), 2);
+//! ```
+
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use crate::histogram::ScoreHistogram;
+use crate::scale::{ClassificationScheme, NpsScheme};
+use crate::{ScoreCount, SegmentCounts, SurveyResponse};
+
+/// Per-rating counts of a set of responses, with optional per-attribute counts and time bounds.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NpsAggregate {
+    histogram: ScoreHistogram,
+    // `None` until the aggregate is given keys or counts a response, so that an empty aggregate
+    // takes on the keys of the first aggregate merged into it.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "no_keys", skip_serializing_if = "has_no_keys")
+    )]
+    attributes: Option<BTreeMap<String, BTreeMap<String, ScoreHistogram>>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    earliest: Option<SystemTime>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    latest: Option<SystemTime>,
+}
+
+impl NpsAggregate {
+    /// Creates an empty aggregate.
+    ///
+    /// An empty aggregate is an identity for [`merge`](NpsAggregate::merge): merging another
+    /// aggregate into it keeps all of that aggregate's attribute keys. Once it counts a response
+    /// of its own, it keeps no attribute keys.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Summarizes `responses`, keeping per-attribute counts for each of `keys`.
+    pub fn from_responses<'a, T: PartialEq + 'a>(
+        responses: impl IntoIterator<Item = &'a SurveyResponse<T>>,
+        keys: &[&str],
+    ) -> Self {
+        let mut aggregate = NpsAggregate {
+            attributes: Some(
+                keys.iter()
+                    .map(|key| (key.to_string(), BTreeMap::new()))
+                    .collect(),
+            ),
+            ..NpsAggregate::new()
+        };
+        for response in responses {
+            aggregate.record(response);
+        }
+        aggregate
+    }
+
+    /// Counts one more response, including its timestamp and the values of the attributes this
+    /// aggregate keeps counts for.
+    pub fn record<T: PartialEq>(&mut self, response: &SurveyResponse<T>) {
+        self.histogram.insert(*response.score());
+        let attributes = self.attributes.get_or_insert_with(BTreeMap::new);
+        for (key, groups) in attributes.iter_mut() {
+            if let Some(value) = response.attribute(key) {
+                groups
+                    .entry(value.to_owned())
+                    .or_default()
+                    .insert(*response.score());
+            }
+        }
+        if let Some(timestamp) = response.timestamp() {
+            self.extend_time_bounds(Some(timestamp), Some(timestamp));
+        }
+    }
+
+    /// Adds the counts of `other` to this aggregate and widens its time bounds to cover both.
+    ///
+    /// Merging is associative and commutative, so partial aggregates can be combined in any order.
+    /// Only the attribute keys kept by both aggregates are kept in the result, since the groups of
+    /// a key kept by one of them would not count the other's responses. Build every partial
+    /// aggregate with the same keys to keep all of them. An empty aggregate created with
+    /// [`new`](NpsAggregate::new) takes on the keys of `other`.
+    pub fn merge(&mut self, other: &NpsAggregate) {
+        self.histogram += other.histogram;
+        match (&mut self.attributes, &other.attributes) {
+            (None, other_attributes) => self.attributes = other_attributes.clone(),
+            (Some(_), None) => {}
+            (Some(attributes), Some(other_attributes)) => {
+                attributes.retain(|key, _| other_attributes.contains_key(key));
+                for (key, groups) in attributes.iter_mut() {
+                    for (value, histogram) in &other_attributes[key] {
+                        *groups.entry(value.clone()).or_default() += *histogram;
+                    }
+                }
+            }
+        }
+        self.extend_time_bounds(other.earliest, other.latest);
+    }
+
+    /// Returns the per-rating counts of all responses.
+    pub fn histogram(&self) -> &ScoreHistogram {
+        &self.histogram
+    }
+
+    /// Returns the per-rating counts of each value of the attribute `key`, or `None` if this
+    /// aggregate does not keep counts for `key`.
+    pub fn group(&self, key: &str) -> Option<&BTreeMap<String, ScoreHistogram>> {
+        self.attributes.as_ref()?.get(key)
+    }
+
+    /// Returns the attribute keys this aggregate keeps counts for.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.attributes
+            .iter()
+            .flat_map(BTreeMap::keys)
+            .map(String::as_str)
+    }
+
+    /// Returns the earliest and latest timestamps of the responses, if any were timestamped.
+    pub fn time_bounds(&self) -> Option<(SystemTime, SystemTime)> {
+        self.earliest.zip(self.latest)
+    }
+
+    /// Returns the total number of responses.
+    pub fn total(&self) -> ScoreCount {
+        self.histogram.total()
+    }
+
+    /// Returns the number of detractors, passives and promoters under the standard NPS scheme.
+    pub fn segment_counts(&self) -> SegmentCounts {
+        self.segment_counts_with(&NpsScheme)
+    }
+
+    /// Returns the number of detractors, passives and promoters, classifying each rating with
+    /// `scheme`.
+    pub fn segment_counts_with(&self, scheme: &impl ClassificationScheme) -> SegmentCounts {
+        self.histogram.segment_counts_with(scheme)
+    }
+
+    /// Returns the exact Net Promoter Score under the standard NPS scheme, or `0.0` if there are no
+    /// responses.
+    pub fn score_exact(&self) -> f64 {
+        self.segment_counts().score_exact()
+    }
+
+    fn extend_time_bounds(&mut self, earliest: Option<SystemTime>, latest: Option<SystemTime>) {
+        self.earliest = match (self.earliest, earliest) {
+            (Some(current), Some(other)) => Some(current.min(other)),
+            (current, other) => current.or(other),
+        };
+        self.latest = match (self.latest, latest) {
+            (Some(current), Some(other)) => Some(current.max(other)),
+            (current, other) => current.or(other),
+        };
+    }
+}
+
+impl FromIterator<NpsAggregate> for NpsAggregate {
+    fn from_iter<I: IntoIterator<Item = NpsAggregate>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(NpsAggregate::new(), |mut total, aggregate| {
+                total.merge(&aggregate);
+                total
+            })
+    }
+}
+
+// Aggregates serialized without attribute counts keep no keys. An empty aggregate that has not
+// been given keys is written with `null` attributes so that it reads back as one.
+#[cfg(feature = "serde")]
+fn no_keys() -> Option<BTreeMap<String, BTreeMap<String, ScoreHistogram>>> {
+    Some(BTreeMap::new())
+}
+
+#[cfg(feature = "serde")]
+fn has_no_keys(attributes: &Option<BTreeMap<String, BTreeMap<String, ScoreHistogram>>>) -> bool {
+    attributes.as_ref().is_some_and(BTreeMap::is_empty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn aggregate(responses: &[(u8, &str, u64)]) -> NpsAggregate {
+        let responses: Vec<_> = responses
+            .iter()
+            .enumerate()
+            .map(|(id, &(score, region, seconds))| {
+                SurveyResponse::new_at(id, score, UNIX_EPOCH + Duration::from_secs(seconds))
+                    .unwrap()
+                    .with_attribute("region", region)
+            })
+            .collect();
+        NpsAggregate::from_responses(&responses, &["region"])
+    }
+
+    #[test]
+    fn test_merge_is_associative_and_commutative() {
+        let a = aggregate(&[(10, "EMEA", 30), (6, "APAC", 10)]);
+        let b = aggregate(&[(8, "EMEA", 50)]);
+        let c = aggregate(&[(9, "AMER", 20), (0, "AMER", 40)]);
+
+        let mut left = a.clone();
+        left.merge(&b);
+        left.merge(&c);
+        let mut bc = b.clone();
+        bc.merge(&c);
+        let mut right = a.clone();
+        right.merge(&bc);
+        let reversed: NpsAggregate = vec![c, b, a].into_iter().collect();
+
+        assert_eq!(left, right);
+        assert_eq!(left, reversed);
+        assert_eq!(left.total(), 5);
+        assert_eq!(left.group("region").unwrap()["AMER"].total(), 2);
+        assert_eq!(
+            left.time_bounds(),
+            Some((
+                UNIX_EPOCH + Duration::from_secs(10),
+                UNIX_EPOCH + Duration::from_secs(50)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_merge_keeps_shared_keys() {
+        let a = aggregate(&[(10, "EMEA", 30)]);
+        let response = SurveyResponse::new(7, 0)
+            .unwrap()
+            .with_attribute("region", "EMEA")
+            .with_attribute("plan", "pro");
+        let b = NpsAggregate::from_responses([&response], &["region", "plan"]);
+
+        let mut merged = b.clone();
+        merged.merge(&a);
+        assert_eq!(merged.keys().collect::<Vec<_>>(), ["region"]);
+        assert_eq!(merged.group("region").unwrap()["EMEA"].total(), 2);
+        assert!(merged.group("plan").is_none());
+
+        let collected: NpsAggregate = vec![b.clone(), b].into_iter().collect();
+        assert_eq!(collected.keys().collect::<Vec<_>>(), ["plan", "region"]);
+        assert_eq!(collected.group("plan").unwrap()["pro"].total(), 2);
+
+        let mut keyless = NpsAggregate::new();
+        keyless.record(&SurveyResponse::new(8, 10).unwrap());
+        keyless.merge(&a);
+        assert_eq!(keyless.total(), 2);
+        assert_eq!(keyless.keys().count(), 0);
+    }
+
+    #[test]
+    fn test_empty_aggregate_is_identity() {
+        let a = aggregate(&[(10, "EMEA", 30), (6, "APAC", 10)]);
+        let b = aggregate(&[(8, "EMEA", 50)]);
+
+        let folded = [&a, &b]
+            .into_iter()
+            .fold(NpsAggregate::default(), |mut total, aggregate| {
+                total.merge(aggregate);
+                total
+            });
+        let mut merged = a.clone();
+        merged.merge(&b);
+        assert_eq!(folded, merged);
+        assert_eq!(folded.group("region").unwrap()["EMEA"].total(), 2);
+
+        let mut right = a.clone();
+        right.merge(&NpsAggregate::new());
+        assert_eq!(right, a);
+    }
+
+    #[test]
+    fn test_empty_aggregate() {
+        let mut aggregate = NpsAggregate::new();
+        assert_eq!(aggregate.time_bounds(), None);
+        assert_eq!(aggregate.score_exact(), 0.0);
+        assert!(aggregate.group("region").is_none());
+
+        aggregate.record(&SurveyResponse::new(1, 9).unwrap());
+        assert_eq!(aggregate.time_bounds(), None);
+        assert_eq!(aggregate.score_exact(), 100.0);
+    }
+}
@@ -0,0 +1,540 @@
+//! `nps`: scores survey responses from CSV or JSON lines files.
+//!
+//! Run `nps --help` for usage. Requires the `cli` feature.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use net_promoter_score::csv::{parse_timestamp, CsvError, CsvErrorKind, CsvImport};
+use net_promoter_score::prelude::*;
+use serde_json::{json, Map, Value};
+
+const USAGE: &str = "\
+Usage: nps [OPTIONS] [FILE]
+
+Computes the Net Promoter Score of the survey responses in FILE, or in standard input if FILE is
+missing or \"-\". Rows that can't be read are reported on standard error and skipped.
+
+Options:
+  -f, --format <FORMAT>     Input format: csv or jsonl. Defaults to jsonl for .jsonl and .ndjson
+                            files and csv otherwise
+  -i, --id <COLUMN>         Respondent ID column [default: respondent_id]
+  -s, --score <COLUMN>      Score column [default: score]
+  -t, --timestamp <COLUMN>  Timestamp column, as Unix seconds or RFC 3339
+  -a, --attribute <COLUMN>  Reads an extra attribute column; may be repeated
+  -d, --delimiter <CHAR>    CSV field delimiter [default: ,]
+  -w, --where <KEY=VALUE>   Keeps only responses whose attribute KEY equals VALUE; may be repeated
+  -g, --group-by <KEY>      Scores each value of attribute KEY separately; may be repeated to
+                            group by combinations of values
+  -l, --level <LEVEL>       Confidence level of the interval, between 0 and 1 [default: 0.95]
+  -o, --output <FORMAT>     Output format: table, json or csv [default: table]
+  -h, --help                Prints this help
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputFormat {
+    Csv,
+    JsonLines,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug)]
+struct Options {
+    path: Option<String>,
+    format: Option<InputFormat>,
+    id_column: String,
+    score_column: String,
+    timestamp_column: Option<String>,
+    attributes: Vec<String>,
+    delimiter: u8,
+    filters: Vec<(String, String)>,
+    group_by: Vec<String>,
+    level: f64,
+    output: OutputFormat,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut options = Options {
+            path: None,
+            format: None,
+            id_column: "respondent_id".to_string(),
+            score_column: "score".to_string(),
+            timestamp_column: None,
+            attributes: Vec::new(),
+            delimiter: b',',
+            filters: Vec::new(),
+            group_by: Vec::new(),
+            level: 0.95,
+            output: OutputFormat::Table,
+        };
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value)),
+                _ => (arg.clone(), None),
+            };
+            let mut value = || match inline_value {
+                Some(value) => Ok(value.to_string()),
+                None => args
+                    .next()
+                    .ok_or_else(|| format!("{} requires a value", flag)),
+            };
+            match flag.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-f" | "--format" => {
+                    options.format = Some(match value()?.as_str() {
+                        "csv" => InputFormat::Csv,
+                        "jsonl" | "ndjson" => InputFormat::JsonLines,
+                        other => return Err(format!("unknown input format \"{}\"", other)),
+                    })
+                }
+                "-i" | "--id" => options.id_column = value()?,
+                "-s" | "--score" => options.score_column = value()?,
+                "-t" | "--timestamp" => options.timestamp_column = Some(value()?),
+                "-a" | "--attribute" => options.attributes.push(value()?),
+                "-d" | "--delimiter" => {
+                    let delimiter = value()?;
+                    options.delimiter = match delimiter.as_bytes() {
+                        [byte] => *byte,
+                        _ if delimiter == "\\t" => b'\t',
+                        _ => return Err(format!("invalid delimiter \"{}\"", delimiter)),
+                    }
+                }
+                "-w" | "--where" => {
+                    let filter = value()?;
+                    let (key, expected) = filter
+                        .split_once('=')
+                        .ok_or_else(|| format!("expected KEY=VALUE, found \"{}\"", filter))?;
+                    options
+                        .filters
+                        .push((key.to_string(), expected.to_string()));
+                }
+                "-g" | "--group-by" => options.group_by.push(value()?),
+                "-l" | "--level" => {
+                    let level = value()?;
+                    options.level = level
+                        .parse()
+                        .ok()
+                        .filter(|level| *level > 0.0 && *level < 1.0)
+                        .ok_or_else(|| {
+                            format!(
+                                "invalid confidence level \"{}\" (expected 0 < level < 1)",
+                                level
+                            )
+                        })?;
+                }
+                "-o" | "--output" => {
+                    options.output = match value()?.as_str() {
+                        "table" => OutputFormat::Table,
+                        "json" => OutputFormat::Json,
+                        "csv" => OutputFormat::Csv,
+                        other => return Err(format!("unknown output format \"{}\"", other)),
+                    }
+                }
+                path if !path.starts_with('-') || path == "-" => {
+                    if options.path.replace(arg).is_some() {
+                        return Err("only one input file may be given".to_string());
+                    }
+                }
+                _ => return Err(format!("unknown option \"{}\"", arg)),
+            }
+        }
+        Ok(Some(options))
+    }
+
+    fn input_format(&self) -> InputFormat {
+        self.format.unwrap_or_else(|| {
+            let extension = self
+                .path
+                .as_deref()
+                .and_then(|path| Path::new(path).extension())
+                .and_then(|extension| extension.to_str());
+            match extension {
+                Some("jsonl" | "ndjson") => InputFormat::JsonLines,
+                _ => InputFormat::Csv,
+            }
+        })
+    }
+
+    // Every attribute that has to be read: the extra columns, the filter keys and the group keys.
+    fn attribute_columns(&self) -> BTreeSet<&str> {
+        self.attributes
+            .iter()
+            .chain(self.filters.iter().map(|(key, _)| key))
+            .chain(&self.group_by)
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("nps: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match run(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("nps: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let input: Box<dyn Read> = match options.path.as_deref() {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(File::open(path).map_err(|error| format!("{}: {}", path, error))?),
+    };
+
+    let mut survey = Survey::new();
+    let result = match options.input_format() {
+        InputFormat::Csv => {
+            let mut import = CsvImport::new(&options.id_column, &options.score_column)
+                .attribute_columns(options.attribute_columns())
+                .delimiter(options.delimiter);
+            if let Some(column) = &options.timestamp_column {
+                import = import.timestamp_column(column);
+            }
+            import.read_into(input, &mut survey)
+        }
+        InputFormat::JsonLines => read_json_lines(options, BufReader::new(input), &mut survey),
+    };
+    if let Err(errors) = result {
+        for error in &errors {
+            if let CsvErrorKind::MissingColumn(_) = error.kind {
+                return Err(error.to_string());
+            }
+            eprintln!("nps: skipped {}", error);
+        }
+    }
+
+    survey.retain(|response| {
+        options
+            .filters
+            .iter()
+            .all(|(key, expected)| response.attribute(key) == Some(expected.as_str()))
+    });
+
+    let rows: Vec<(Vec<String>, SegmentCounts)> = if options.group_by.is_empty() {
+        vec![(Vec::new(), survey.segment_counts())]
+    } else {
+        let keys: Vec<&str> = options.group_by.iter().map(String::as_str).collect();
+        survey.group_by_keys(&keys).into_iter().collect()
+    };
+
+    let output = match options.output {
+        OutputFormat::Table => table(options, &rows),
+        OutputFormat::Json => json_lines(options, &rows),
+        OutputFormat::Csv => csv(options, &rows),
+    }?;
+    io::stdout()
+        .lock()
+        .write_all(output.as_bytes())
+        .map_err(|error| error.to_string())
+}
+
+fn read_json_lines(
+    options: &Options,
+    input: impl BufRead,
+    survey: &mut Survey<String>,
+) -> Result<Vec<String>, Vec<CsvError>> {
+    let attributes = options.attribute_columns();
+    let mut duplicates = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index as u64 + 1;
+        let error = |kind| CsvError {
+            line: line_number,
+            kind,
+        };
+        let line = match line {
+            Ok(line) if line.trim().is_empty() => continue,
+            Ok(line) => line,
+            Err(io_error) => {
+                errors.push(error(CsvErrorKind::Malformed(io_error.to_string())));
+                break;
+            }
+        };
+
+        let response = (|| {
+            let object: Map<String, Value> = serde_json::from_str(&line)
+                .map_err(|json_error| error(CsvErrorKind::Malformed(json_error.to_string())))?;
+            let field = |name: &str| {
+                object.get(name).and_then(|value| match value {
+                    Value::String(text) if text.trim().is_empty() => None,
+                    Value::String(text) => Some(text.trim().to_string()),
+                    Value::Null => None,
+                    other => Some(other.to_string()),
+                })
+            };
+            let required = |name: &String| {
+                field(name).ok_or_else(|| error(CsvErrorKind::MissingValue(name.clone())))
+            };
+
+            let respondent_id = required(&options.id_column)?;
+            let raw_score = required(&options.score_column)?;
+            let score: NpsRating = raw_score
+                .parse()
+                .map_err(|_| error(CsvErrorKind::InvalidScore(raw_score.clone())))?;
+            let timestamp = match options.timestamp_column.as_deref().and_then(field) {
+                Some(raw) => Some(
+                    parse_timestamp(&raw)
+                        .ok_or_else(|| error(CsvErrorKind::InvalidTimestamp(raw.clone())))?,
+                ),
+                None => None,
+            };
+            let mut response = match timestamp {
+                Some(timestamp) => SurveyResponse::new_at(respondent_id, score, timestamp),
+                None => SurveyResponse::new(respondent_id, score),
+            }
+            .map_err(|rating_error| error(CsvErrorKind::Rating(rating_error)))?;
+            for &name in &attributes {
+                if let Some(value) = field(name) {
+                    response = response.with_attribute(name, value);
+                }
+            }
+            Ok(response)
+        })();
+
+        match response.and_then(|response| {
+            survey
+                .add_survey_response(response)
+                .map_err(|rating_error| error(CsvErrorKind::Rating(rating_error)))
+        }) {
+            Ok(duplicate) => duplicates.extend(duplicate),
+            Err(row_error) => errors.push(row_error),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(duplicates)
+    } else {
+        Err(errors)
+    }
+}
+
+// One output row: the group's attribute values, its counts, and its confidence interval if there
+// are enough responses to compute one.
+struct Row<'a> {
+    group: &'a [String],
+    counts: SegmentCounts,
+    interval: Option<ConfidenceInterval>,
+}
+
+fn rows<'a>(
+    options: &Options,
+    rows: &'a [(Vec<String>, SegmentCounts)],
+) -> Result<Vec<Row<'a>>, String> {
+    rows.iter()
+        .map(|(group, counts)| {
+            let interval =
+                match counts.confidence_interval(options.level, IntervalMethod::default()) {
+                    Ok(interval) => Some(interval),
+                    Err(NetPromoterScoreError::InsufficientResponses(_)) => None,
+                    Err(error) => return Err(error.to_string()),
+                };
+            Ok(Row {
+                group: group.as_slice(),
+                counts: *counts,
+                interval,
+            })
+        })
+        .collect()
+}
+
+fn percent(count: ScoreCount, total: ScoreCount) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        100.0 * count as f64 / total as f64
+    }
+}
+
+// Formats a confidence level as a percentage with at most one decimal, e.g. 0.95 as "95".
+fn percent_label(level: f64) -> String {
+    let label = format!("{:.1}", level * 100.0);
+    label
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn table(options: &Options, data: &[(Vec<String>, SegmentCounts)]) -> Result<String, String> {
+    let mut header: Vec<String> = options.group_by.clone();
+    header.extend(
+        [
+            "responses",
+            "promoters",
+            "passives",
+            "detractors",
+            "nps",
+            "ci",
+        ]
+        .map(String::from),
+    );
+    header
+        .last_mut()
+        .unwrap()
+        .insert_str(0, &format!("{}% ", percent_label(options.level)));
+
+    let mut lines = vec![header];
+    for row in rows(options, data)? {
+        let total = row.counts.total();
+        let mut line: Vec<String> = row.group.to_vec();
+        line.push(total.to_string());
+        for count in [
+            row.counts.promoters,
+            row.counts.passives,
+            row.counts.detractors,
+        ] {
+            line.push(format!("{} ({:.1}%)", count, percent(count, total)));
+        }
+        line.push(format!("{:.1}", row.counts.score_exact()));
+        line.push(match row.interval {
+            Some(interval) => format!("{:.1} to {:.1}", interval.lower, interval.upper),
+            None => "-".to_string(),
+        });
+        lines.push(line);
+    }
+
+    let mut widths = vec![0; lines[0].len()];
+    for line in &lines {
+        for (width, cell) in widths.iter_mut().zip(line) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let groups = options.group_by.len();
+    let mut output = String::new();
+    for line in &lines {
+        let cells: Vec<String> = line
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, &width))| {
+                if column < groups {
+                    format!("{:<width$}", cell, width = width)
+                } else {
+                    format!("{:>width$}", cell, width = width)
+                }
+            })
+            .collect();
+        writeln!(output, "{}", cells.join("  ").trim_end()).unwrap();
+    }
+    Ok(output)
+}
+
+fn json_lines(options: &Options, data: &[(Vec<String>, SegmentCounts)]) -> Result<String, String> {
+    let mut output = String::new();
+    for row in rows(options, data)? {
+        let total = row.counts.total();
+        let mut object = Map::new();
+        if !options.group_by.is_empty() {
+            let group: Map<String, Value> = options
+                .group_by
+                .iter()
+                .cloned()
+                .zip(row.group.iter().cloned().map(Value::String))
+                .collect();
+            object.insert("group".to_string(), Value::Object(group));
+        }
+        object.insert("responses".to_string(), json!(total));
+        object.insert(
+            "promoters".to_string(),
+            json!({"count": row.counts.promoters, "percent": percent(row.counts.promoters, total)}),
+        );
+        object.insert(
+            "passives".to_string(),
+            json!({"count": row.counts.passives, "percent": percent(row.counts.passives, total)}),
+        );
+        object.insert(
+            "detractors".to_string(),
+            json!({"count": row.counts.detractors, "percent": percent(row.counts.detractors, total)}),
+        );
+        object.insert("nps".to_string(), json!(row.counts.score_exact()));
+        object.insert(
+            "confidence_interval".to_string(),
+            match row.interval {
+                Some(interval) => json!({
+                    "level": interval.level,
+                    "lower": interval.lower,
+                    "upper": interval.upper,
+                    "standard_error": interval.standard_error,
+                    "margin_of_error": interval.margin_of_error,
+                }),
+                None => Value::Null,
+            },
+        );
+        writeln!(output, "{}", Value::Object(object)).unwrap();
+    }
+    Ok(output)
+}
+
+fn csv(options: &Options, data: &[(Vec<String>, SegmentCounts)]) -> Result<String, String> {
+    let mut writer = ::csv::Writer::from_writer(Vec::new());
+    let mut header: Vec<&str> = options.group_by.iter().map(String::as_str).collect();
+    header.extend([
+        "responses",
+        "promoters",
+        "passives",
+        "detractors",
+        "promoters_percent",
+        "passives_percent",
+        "detractors_percent",
+        "nps",
+        "ci_level",
+        "ci_lower",
+        "ci_upper",
+    ]);
+    writer
+        .write_record(&header)
+        .map_err(|error| error.to_string())?;
+
+    for row in rows(options, data)? {
+        let counts = row.counts;
+        let total = counts.total();
+        let mut record: Vec<String> = row.group.to_vec();
+        record.extend(
+            [total, counts.promoters, counts.passives, counts.detractors]
+                .iter()
+                .map(ToString::to_string),
+        );
+        record.extend(
+            [counts.promoters, counts.passives, counts.detractors]
+                .iter()
+                .map(|&count| percent(count, total).to_string()),
+        );
+        record.push(counts.score_exact().to_string());
+        record.push(options.level.to_string());
+        match row.interval {
+            Some(interval) => {
+                record.push(interval.lower.to_string());
+                record.push(interval.upper.to_string());
+            }
+            None => record.extend([String::new(), String::new()]),
+        }
+        writer
+            .write_record(&record)
+            .map_err(|error| error.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|error| error.to_string())?;
+    String::from_utf8(bytes).map_err(|error| error.to_string())
+}
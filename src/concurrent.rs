@@ -0,0 +1,233 @@
+//! A survey that many threads can add responses to at once.
+//!
+//! A [`ConcurrentSurvey`] splits its responses across shards, each an ordinary [`Survey`] behind
+//! its own lock, and picks the shard from a hash of the respondent ID. Inserts from different
+//! threads only contend when they land on the same shard, and every response of a respondent goes
+//! to the same shard so the [`DuplicatePolicy`] is applied as usual.
+//!
+//! Per-rating counts are mirrored in atomic counters, so scores can be read from `&self` without
+//! taking any lock. [`snapshot`](ConcurrentSurvey::snapshot) copies the responses into an ordinary
+//! `Survey` for everything else.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//! use std::thread;
+//!
+//! let survey = ConcurrentSurvey::new();
+//! thread::scope(|scope| {
+//!     for worker in 0..4 {
+//!         let survey = &survey;
+//!         scope.spawn(move || {
+//!             for respondent in 0..100 {
+//!                 let score = if worker % 2 == 0 { 10 } else { 0 };
+//!                 survey.add_response(worker * 100 + respondent, score).unwrap();
+//!             }
+//!         });
+//!     }
+//! });
+//!
+//! assert_eq!(survey.len(), 400);
+//! assert_eq!(survey.score(), 0);
+//! assert_eq!(survey.snapshot().segment_counts(), survey.segment_counts());
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+use crate::histogram::ScoreHistogram;
+use crate::scale::{ClassificationScheme, NpsScheme};
+use crate::{
+    DuplicatePolicy, NetPromoterScoreError, NpsRating, RoundingPolicy, ScoreCount, SegmentCounts,
+    Survey, SurveyResponse,
+};
+
+const DEFAULT_SHARDS: usize = 16;
+
+/// A thread-safe survey that accepts responses from many threads and reads scores without locking.
+///
+/// Score reads never block. While inserts are in flight, a read may reflect some of them and not
+/// others; once they have returned, reads are exact.
+pub struct ConcurrentSurvey<T, S = NpsScheme> {
+    shards: Box<[Mutex<Survey<T, S>>]>,
+    counts: [AtomicUsize; 11],
+    scheme: S,
+    rounding_policy: RoundingPolicy,
+    hasher: RandomState,
+}
+
+impl<T: Hash + Ord + Clone> ConcurrentSurvey<T> {
+    /// Creates a new empty survey using the standard [`NpsScheme`].
+    pub fn new() -> Self {
+        Self::with_scheme(NpsScheme, DEFAULT_SHARDS)
+    }
+}
+
+impl<T: Hash + Ord + Clone> Default for ConcurrentSurvey<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S> ConcurrentSurvey<T, S>
+where
+    T: Hash + Ord + Clone,
+    S: ClassificationScheme + Clone,
+{
+    /// Creates a new empty survey that validates and classifies ratings with `scheme`, with its
+    /// responses split across `shards` locks. At least one shard is always used.
+    pub fn with_scheme(scheme: S, shards: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1))
+                .map(|_| Mutex::new(Survey::with_scheme(scheme.clone())))
+                .collect(),
+            counts: Default::default(),
+            scheme,
+            rounding_policy: Default::default(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Sets the `DuplicatePolicy` applied when a respondent who already has a response is added
+    /// again. It only affects responses added afterwards.
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        for shard in self.shards.iter_mut() {
+            shard
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner)
+                .set_duplicate_policy(policy);
+        }
+    }
+
+    /// Sets the `RoundingPolicy` used by [`score`](ConcurrentSurvey::score).
+    pub fn set_rounding_policy(&mut self, policy: RoundingPolicy) {
+        self.rounding_policy = policy;
+    }
+
+    /// Adds a response with the given respondent ID and score. Can be called from many threads
+    /// at once.
+    ///
+    /// Returns the same result as [`Survey::add_response`].
+    pub fn add_response(
+        &self,
+        respondent_id: T,
+        score: NpsRating,
+    ) -> Result<Option<T>, NetPromoterScoreError> {
+        let response = SurveyResponse::new(respondent_id, score)?;
+        self.add_survey_response(response)
+    }
+
+    /// Adds a response with the given respondent ID, score and timestamp. Can be called from many
+    /// threads at once.
+    ///
+    /// Returns the same result as [`Survey::add_response_at`].
+    pub fn add_response_at(
+        &self,
+        respondent_id: T,
+        score: NpsRating,
+        timestamp: SystemTime,
+    ) -> Result<Option<T>, NetPromoterScoreError> {
+        let response = SurveyResponse::new_at(respondent_id, score, timestamp)?;
+        self.add_survey_response(response)
+    }
+
+    /// Adds an already validated `SurveyResponse`, keyed by its respondent ID. Can be called from
+    /// many threads at once.
+    ///
+    /// Returns the same result as [`Survey::add_survey_response`].
+    pub fn add_survey_response(
+        &self,
+        response: SurveyResponse<T>,
+    ) -> Result<Option<T>, NetPromoterScoreError> {
+        let mut shard = self.shard(response.respondent_id());
+        let before = *shard.histogram().counts();
+        let result = shard.add_survey_response(response);
+        for ((counter, old), new) in self
+            .counts
+            .iter()
+            .zip(before)
+            .zip(shard.histogram().counts())
+        {
+            if *new > old {
+                counter.fetch_add(new - old, Ordering::Relaxed);
+            } else if *new < old {
+                counter.fetch_sub(old - new, Ordering::Relaxed);
+            }
+        }
+        result
+    }
+
+    /// Returns the per-rating counts of the survey responses, without locking.
+    pub fn histogram(&self) -> ScoreHistogram {
+        ScoreHistogram::from_counts(std::array::from_fn(|rating| {
+            self.counts[rating].load(Ordering::Relaxed)
+        }))
+    }
+
+    /// Returns the number of respondents with a response, without locking.
+    pub fn len(&self) -> ScoreCount {
+        self.histogram().total()
+    }
+
+    /// Returns `true` if the survey has no responses.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of detractors, passives and promoters, without locking.
+    pub fn segment_counts(&self) -> SegmentCounts {
+        self.histogram().segment_counts_with(&self.scheme)
+    }
+
+    /// Returns the exact, unrounded Net Promoter Score (NPS), without locking.
+    pub fn score_exact(&self) -> f64 {
+        self.segment_counts().score_exact()
+    }
+
+    /// Returns the Net Promoter Score (NPS) rounded with the survey's `RoundingPolicy`, without
+    /// locking.
+    pub fn score(&self) -> i32 {
+        self.rounding_policy.round(self.score_exact()) as i32
+    }
+
+    /// Returns an ordinary `Survey` holding a copy of every response, including the history kept
+    /// under [`DuplicatePolicy::KeepAll`].
+    ///
+    /// Each shard is locked in turn while it is copied, so inserts that run during the snapshot
+    /// may or may not be included.
+    pub fn snapshot(&self) -> Survey<T, S> {
+        let mut snapshot = Survey::with_scheme(self.scheme.clone());
+        snapshot.set_rounding_policy(self.rounding_policy);
+        for shard in self.shards.iter() {
+            let shard = shard.lock().unwrap_or_else(PoisonError::into_inner);
+            snapshot.set_duplicate_policy(shard.duplicate_policy());
+            // Shards hold disjoint respondents, so their contents can be combined directly.
+            snapshot.responses.extend(
+                shard
+                    .responses
+                    .iter()
+                    .map(|(id, response)| (id.clone(), response.clone())),
+            );
+            snapshot.history.extend(
+                shard
+                    .history
+                    .iter()
+                    .map(|(id, history)| (id.clone(), history.clone())),
+            );
+            snapshot.histogram += shard.histogram;
+        }
+        snapshot
+    }
+
+    // Locks the shard that holds the responses of `respondent_id`.
+    fn shard(&self, respondent_id: &T) -> MutexGuard<'_, Survey<T, S>> {
+        let index = self.hasher.hash_one(respondent_id) as usize % self.shards.len();
+        self.shards[index]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
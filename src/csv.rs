@@ -0,0 +1,697 @@
+//! CSV import and export of surveys. Requires the `csv` feature.
+//!
+//! [`CsvImport`] reads a CSV file into a `Survey<String>`, taking the respondent ID, score and
+//! optional timestamp, comment and attributes from named columns. Rows that can't be read produce a
+//! [`CsvError`] carrying their line number. [`write_survey`] writes a survey back out, including
+//! the computed classification of every response.
+//!
+//! Timestamps are read either as Unix seconds or as RFC 3339 dates and times such as `2024-05-01`,
+//! `2024-05-01T09:30:00Z` or `2024-05-01T09:30:00.250+02:00`, and are written in UTC as
+//! `2024-05-01T07:30:00Z`.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::csv::{write_survey, CsvImport};
+//! use net_promoter_score::prelude::*;
+//!
+//! let data = "\
+//! id,nps,submitted,region
+//! c1,10,2024-05-01,EMEA
+//! c2,6,2024-05-02,APAC
+//! c3,9,2024-05-02,EMEA
+//! ";
+//!
+//! let import = CsvImport::new("id", "nps")
+//!     .timestamp_column("submitted")
+//!     .attribute_column("region");
+//! let mut survey = import.read(data.as_bytes()).unwrap();
+//! assert_eq!(survey.score(), 33);
+//! assert_eq!(survey.group_by("region")["EMEA"].promoters, 2);
+//!
+//! let mut output = Vec::new();
+//! write_survey(&survey, &mut output).unwrap();
+//! let output = String::from_utf8(output).unwrap();
+//! assert!(output.starts_with("respondent_id,score,classification,timestamp,region\n"));
+//! assert!(output.contains("c2,6,Detractor,2024-05-02T00:00:00Z,APAC\n"));
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::scale::ClassificationScheme;
+use crate::timeseries::{civil_from_days, days_from_civil, days_since_epoch};
+use crate::{NetPromoterScoreError, NpsRating, Survey, SurveyResponse};
+
+/// Describes which columns of a CSV file hold the parts of each survey response.
+///
+/// The file must have a header row; columns are matched by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvImport {
+    id_column: String,
+    score_column: String,
+    timestamp_column: Option<String>,
+    comment_column: Option<String>,
+    attribute_columns: Vec<String>,
+    delimiter: u8,
+}
+
+impl CsvImport {
+    /// Creates an import reading respondent IDs and scores from the named columns.
+    pub fn new(id_column: impl Into<String>, score_column: impl Into<String>) -> Self {
+        Self {
+            id_column: id_column.into(),
+            score_column: score_column.into(),
+            timestamp_column: None,
+            comment_column: None,
+            attribute_columns: Vec::new(),
+            delimiter: b',',
+        }
+    }
+
+    /// Reads response timestamps from the named column. Empty cells leave the response untimed.
+    pub fn timestamp_column(mut self, column: impl Into<String>) -> Self {
+        self.timestamp_column = Some(column.into());
+        self
+    }
+
+    /// Reads free-text response comments from the named column. Empty cells leave the response
+    /// without a comment.
+    pub fn comment_column(mut self, column: impl Into<String>) -> Self {
+        self.comment_column = Some(column.into());
+        self
+    }
+
+    /// Reads the named column into a response attribute of the same name. Empty cells are skipped.
+    pub fn attribute_column(mut self, column: impl Into<String>) -> Self {
+        self.attribute_columns.push(column.into());
+        self
+    }
+
+    /// Reads each of the named columns into a response attribute of the same name.
+    pub fn attribute_columns<S: Into<String>>(
+        mut self,
+        columns: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.attribute_columns
+            .extend(columns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the field delimiter, which defaults to a comma.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Reads a new survey from CSV data.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CsvError` for every row that could not be read. See
+    /// [`read_into`](CsvImport::read_into) to keep the valid rows instead.
+    pub fn read(&self, reader: impl io::Read) -> Result<Survey<String>, Vec<CsvError>> {
+        let mut survey = Survey::new();
+        self.read_into(reader, &mut survey)?;
+        Ok(survey)
+    }
+
+    /// Adds the responses in CSV data to an existing survey.
+    ///
+    /// Valid rows are added even if other rows fail, as with
+    /// [`Survey::add_multiple_responses`]. On success, the IDs of respondents that already had a
+    /// response are returned; how they were handled depends on the survey's
+    /// [`DuplicatePolicy`](crate::DuplicatePolicy).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CsvError` for every row that could not be read or added, or a single error on
+    /// line 1 if the header is missing a configured column.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::csv::{CsvErrorKind, CsvImport};
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let data = "id,score\nc1,9\nc2,eleven\nc3,12\n";
+    /// let mut survey = Survey::new();
+    /// let errors = CsvImport::new("id", "score")
+    ///     .read_into(data.as_bytes(), &mut survey)
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(errors[0].line, 3);
+    /// assert_eq!(errors[0].kind, CsvErrorKind::InvalidScore("eleven".to_string()));
+    /// assert_eq!(errors[1].line, 4);
+    /// assert_eq!(survey.len(), 1);
+    /// ```
+    pub fn read_into<S: ClassificationScheme>(
+        &self,
+        reader: impl io::Read,
+        survey: &mut Survey<String, S>,
+    ) -> Result<Vec<String>, Vec<CsvError>> {
+        let mut reader = ::csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .flexible(true)
+            .from_reader(reader);
+
+        let headers = reader
+            .headers()
+            .map_err(|error| vec![CsvError::from_csv(1, error)])?
+            .clone();
+        let column = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header.trim() == name)
+                .ok_or_else(|| {
+                    vec![CsvError::new(
+                        1,
+                        CsvErrorKind::MissingColumn(name.to_owned()),
+                    )]
+                })
+        };
+        let id_index = column(&self.id_column)?;
+        let score_index = column(&self.score_column)?;
+        let timestamp_index = self.timestamp_column.as_deref().map(column).transpose()?;
+        let comment_index = self.comment_column.as_deref().map(column).transpose()?;
+        let attribute_indices = self
+            .attribute_columns
+            .iter()
+            .map(|name| Ok((name.as_str(), column(name)?)))
+            .collect::<Result<Vec<_>, Vec<CsvError>>>()?;
+
+        let mut duplicates = Vec::new();
+        let mut errors = Vec::new();
+        let mut record = ::csv::StringRecord::new();
+        loop {
+            let line = reader.position().line();
+            match reader.read_record(&mut record) {
+                Ok(false) => break,
+                Ok(true) => {}
+                Err(error) => {
+                    errors.push(CsvError::from_csv(line, error));
+                    continue;
+                }
+            }
+            // The reader reports a record preceded by blank lines as starting on the first blank
+            // line, so count back from the end of the record as well.
+            let newlines: u64 = record
+                .iter()
+                .map(|field| field.matches('\n').count() as u64)
+                .sum();
+            let line = record
+                .position()
+                .map_or(line, |position| position.line())
+                .max(reader.position().line().saturating_sub(newlines + 1));
+            let value = |index: usize| {
+                record
+                    .get(index)
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+            };
+            let field = |index: usize, name: &str| {
+                value(index)
+                    .ok_or_else(|| CsvError::new(line, CsvErrorKind::MissingValue(name.to_owned())))
+            };
+
+            let response = (|| {
+                let respondent_id = field(id_index, &self.id_column)?;
+                let raw_score = field(score_index, &self.score_column)?;
+                let score: NpsRating = raw_score.parse().map_err(|_| {
+                    CsvError::new(line, CsvErrorKind::InvalidScore(raw_score.to_owned()))
+                })?;
+                let mut response = SurveyResponse::new(respondent_id.to_owned(), score)
+                    .map_err(|error| CsvError::new(line, CsvErrorKind::Rating(error)))?;
+                if let Some(index) = timestamp_index {
+                    if let Some(raw) = value(index) {
+                        let timestamp = parse_timestamp(raw).ok_or_else(|| {
+                            CsvError::new(line, CsvErrorKind::InvalidTimestamp(raw.to_owned()))
+                        })?;
+                        response.timestamp = Some(timestamp);
+                    }
+                }
+                if let Some(comment) = comment_index.and_then(value) {
+                    response = response.with_comment(comment);
+                }
+                for &(name, index) in &attribute_indices {
+                    if let Some(value) = value(index) {
+                        response = response.with_attribute(name, value);
+                    }
+                }
+                Ok(response)
+            })();
+
+            match response.and_then(|response| {
+                survey
+                    .add_survey_response(response)
+                    .map_err(|error| CsvError::new(line, CsvErrorKind::Rating(error)))
+            }) {
+                Ok(duplicate) => duplicates.extend(duplicate),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(duplicates)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// An error reading one row of a CSV file.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CsvError {
+    /// The 1-based line number of the row; the header is line 1.
+    pub line: u64,
+    /// What went wrong.
+    pub kind: CsvErrorKind,
+}
+
+impl CsvError {
+    fn new(line: u64, kind: CsvErrorKind) -> Self {
+        Self { line, kind }
+    }
+
+    fn from_csv(line: u64, error: ::csv::Error) -> Self {
+        let line = error.position().map_or(line, |position| position.line());
+        Self::new(line, CsvErrorKind::Malformed(error.to_string()))
+    }
+}
+
+/// The reasons a CSV row can fail to be read.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CsvErrorKind {
+    /// The header has no column with this name.
+    MissingColumn(String),
+    /// The row has no value in this column.
+    MissingValue(String),
+    /// The score is not a whole number between 0 and 255.
+    InvalidScore(String),
+    /// The timestamp is neither Unix seconds nor an RFC 3339 date or date and time.
+    InvalidTimestamp(String),
+    /// The response could not be added to the survey, e.g. because its rating is out of range.
+    Rating(NetPromoterScoreError),
+    /// The row is not valid CSV or could not be read.
+    Malformed(String),
+}
+
+impl std::error::Error for CsvError {}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            CsvErrorKind::MissingColumn(column) => write!(f, "missing column \"{}\"", column),
+            CsvErrorKind::MissingValue(column) => {
+                write!(f, "missing value in column \"{}\"", column)
+            }
+            CsvErrorKind::InvalidScore(value) => write!(f, "invalid score \"{}\"", value),
+            CsvErrorKind::InvalidTimestamp(value) => write!(f, "invalid timestamp \"{}\"", value),
+            CsvErrorKind::Rating(error) => write!(f, "{}", error),
+            CsvErrorKind::Malformed(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Writes a survey as CSV with the columns `respondent_id`, `score`, `classification`,
+/// `timestamp`, `comment` if any response has a comment, and one column per attribute key found in
+/// the survey, in key order.
+///
+/// Missing timestamps, comments and attributes are written as empty cells.
+///
+/// # Errors
+///
+/// Returns an error of kind [`io::ErrorKind::InvalidInput`] if an attribute key has the same name
+/// as one of the other columns, such as `comment` or `timestamp`, and any error raised by
+/// `writer`.
+pub fn write_survey<T, S>(survey: &Survey<T, S>, writer: impl io::Write) -> io::Result<()>
+where
+    T: PartialEq + Ord + Clone + Display,
+    S: ClassificationScheme,
+{
+    let attribute_keys: BTreeSet<&str> = survey
+        .responses()
+        .flat_map(|response| response.attributes().keys().map(String::as_str))
+        .collect();
+    let has_comments = survey
+        .responses()
+        .any(|response| response.comment().is_some());
+
+    let mut writer = ::csv::Writer::from_writer(writer);
+    let mut header = vec!["respondent_id", "score", "classification", "timestamp"];
+    if has_comments {
+        header.push("comment");
+    }
+    if let Some(key) = attribute_keys.iter().find(|key| header.contains(key)) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "attribute \"{}\" clashes with the column of the same name",
+                key
+            ),
+        ));
+    }
+    header.extend(&attribute_keys);
+    writer.write_record(&header)?;
+
+    for response in survey.responses() {
+        let mut row = vec![
+            response.respondent_id().to_string(),
+            response.score().to_string(),
+            survey.classify(*response.score()).to_string(),
+            response
+                .timestamp()
+                .map(format_timestamp)
+                .unwrap_or_default(),
+        ];
+        if has_comments {
+            row.push(response.comment().unwrap_or_default().to_owned());
+        }
+        row.extend(
+            attribute_keys
+                .iter()
+                .map(|key| response.attribute(key).unwrap_or_default().to_owned()),
+        );
+        writer.write_record(&row)?;
+    }
+    writer.flush()
+}
+
+/// Parses a timestamp in any format accepted by [`CsvImport::timestamp_column`]: Unix seconds or
+/// an RFC 3339 date (`2024-05-01`) or date and time (`2024-05-01T09:30:00Z`, with optional
+/// fractional seconds and a `Z` or `±HH:MM` offset).
+///
+/// Returns `None` if `value` is in none of these formats.
+pub fn parse_timestamp(value: &str) -> Option<SystemTime> {
+    if let Ok(seconds) = value.parse::<i64>() {
+        return Some(from_unix_seconds(seconds, 0));
+    }
+
+    let number = |text: &str| -> Option<u32> {
+        (!text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit()))
+            .then(|| text.parse().ok())
+            .flatten()
+    };
+    let (date, time) = match value.find(['T', 't', ' ']) {
+        Some(index) => (&value[..index], Some(&value[index + 1..])),
+        None => (value, None),
+    };
+    let mut date_parts = date.splitn(3, '-');
+    let year = number(date_parts.next()?)? as i64;
+    let month = number(date_parts.next()?)?;
+    let day = number(date_parts.next()?)?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    let mut seconds = days_from_civil(year, month, day) * 86_400;
+    let mut nanos = 0;
+
+    if let Some(time) = time {
+        let (clock, offset) = match time.find(['Z', 'z', '+', '-']) {
+            Some(index) => time.split_at(index),
+            None => return None,
+        };
+        let (clock, fraction) = match clock.split_once('.') {
+            Some((clock, fraction)) => (clock, Some(fraction)),
+            None => (clock, None),
+        };
+        let mut clock_parts = clock.splitn(3, ':');
+        let hours = number(clock_parts.next()?)?;
+        let minutes = number(clock_parts.next()?)?;
+        let secs = number(clock_parts.next()?)?;
+        if hours > 23 || minutes > 59 || secs > 60 {
+            return None;
+        }
+        seconds += (hours * 3600 + minutes * 60 + secs) as i64;
+
+        if let Some(fraction) = fraction {
+            number(fraction)?;
+            let digits: String = fraction
+                .chars()
+                .chain("000000000".chars())
+                .take(9)
+                .collect();
+            nanos = digits.parse().ok()?;
+        }
+
+        if !offset.eq_ignore_ascii_case("z") {
+            let (sign, offset) = offset.split_at(1);
+            let (offset_hours, offset_minutes) = offset.split_once(':')?;
+            let (offset_hours, offset_minutes) = (number(offset_hours)?, number(offset_minutes)?);
+            if offset_hours > 23 || offset_minutes > 59 {
+                return None;
+            }
+            let offset_seconds = offset_hours
+                .checked_mul(3600)?
+                .checked_add(offset_minutes.checked_mul(60)?)?
+                as i64;
+            seconds -= if sign == "+" {
+                offset_seconds
+            } else {
+                -offset_seconds
+            };
+        }
+    }
+    Some(from_unix_seconds(seconds, nanos))
+}
+
+/// Formats a time in UTC as `YYYY-MM-DDTHH:MM:SSZ`, dropping fractional seconds.
+pub(crate) fn format_timestamp(time: SystemTime) -> String {
+    let days = days_since_epoch(time);
+    let seconds_of_day = match time.duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => elapsed.as_secs() as i64,
+        Err(error) => -(error.duration().as_secs_f64().ceil() as i64),
+    } - days * 86_400;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+fn from_unix_seconds(seconds: i64, nanos: u32) -> SystemTime {
+    if seconds >= 0 {
+        UNIX_EPOCH + Duration::new(seconds as u64, nanos)
+    } else {
+        UNIX_EPOCH - Duration::from_secs(seconds.unsigned_abs())
+            + Duration::from_nanos(nanos.into())
+    }
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("86400"), Some(at(86_400)));
+        assert_eq!(parse_timestamp("1970-01-02"), Some(at(86_400)));
+        assert_eq!(parse_timestamp("1970-01-02T01:00:30Z"), Some(at(90_030)));
+        assert_eq!(
+            parse_timestamp("1970-01-02 03:00:30+02:00"),
+            Some(at(90_030))
+        );
+        assert_eq!(
+            parse_timestamp("1970-01-01T23:00:30-02:00"),
+            Some(at(90_030))
+        );
+        assert_eq!(
+            parse_timestamp("1970-01-02T01:00:30.25Z"),
+            Some(at(90_030) + Duration::from_millis(250))
+        );
+        assert_eq!(
+            parse_timestamp("1969-12-31"),
+            Some(UNIX_EPOCH - Duration::from_secs(86_400))
+        );
+
+        for invalid in [
+            "",
+            "yesterday",
+            "2024-02-30",
+            "2024-13-01",
+            "2024-01-01T10:00",
+            "2024-01-01T10:00:00",
+            "2024-01-01T25:00:00Z",
+            "2024-01-01T00:00:00+4294967:00",
+            "2024-01-01T00:00:00+99:99",
+            "2024-01-01T00:00:00+24:00",
+            "2024-01-01T00:00:00-23:60",
+            "2024-1x-01",
+        ] {
+            assert_eq!(parse_timestamp(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(at(90_030)), "1970-01-02T01:00:30Z");
+        assert_eq!(
+            format_timestamp(UNIX_EPOCH - Duration::from_secs(1)),
+            "1969-12-31T23:59:59Z"
+        );
+        let time = parse_timestamp("2024-02-29T12:34:56Z").unwrap();
+        assert_eq!(format_timestamp(time), "2024-02-29T12:34:56Z");
+    }
+
+    #[test]
+    fn test_line_numbered_errors() {
+        let data = "\
+id;score;when
+a;10;2024-01-01
+
+b;;2024-01-01
+c;7;not a date
+;5;
+d;99;
+e;x;
+f;0
+";
+        let mut survey = Survey::new();
+        let errors = CsvImport::new("id", "score")
+            .timestamp_column("when")
+            .delimiter(b';')
+            .read_into(data.as_bytes(), &mut survey)
+            .unwrap_err();
+
+        let summary: Vec<(u64, &CsvErrorKind)> = errors
+            .iter()
+            .map(|error| (error.line, &error.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (4, &CsvErrorKind::MissingValue("score".to_string())),
+                (5, &CsvErrorKind::InvalidTimestamp("not a date".to_string())),
+                (6, &CsvErrorKind::MissingValue("id".to_string())),
+                (
+                    7,
+                    &CsvErrorKind::Rating(NetPromoterScoreError::InvalidRating(99))
+                ),
+                (8, &CsvErrorKind::InvalidScore("x".to_string())),
+            ]
+        );
+        assert_eq!(
+            errors[3].to_string(),
+            "line 7: Invalid rating value: 99 (expected 0 to 10)"
+        );
+        assert_eq!(survey.len(), 2);
+        assert_eq!(survey.get(&"f".to_string()).unwrap().timestamp(), None);
+    }
+
+    #[test]
+    fn test_missing_column() {
+        let errors = CsvImport::new("id", "score")
+            .attribute_column("region")
+            .read("id,score\na,1\n".as_bytes())
+            .err()
+            .unwrap();
+        assert_eq!(
+            errors,
+            vec![CsvError::new(
+                1,
+                CsvErrorKind::MissingColumn("region".to_string())
+            )]
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut survey: Survey<String> = Survey::new();
+        survey
+            .add_survey_response(
+                SurveyResponse::new_at("a".to_string(), 9, at(1_700_000_000))
+                    .unwrap()
+                    .with_attribute("region", "EMEA")
+                    .with_attribute("plan", "pro"),
+            )
+            .unwrap();
+        survey
+            .add_response_with_attributes("b, inc".to_string(), 4, [("region", "APAC")])
+            .unwrap();
+
+        let mut output = Vec::new();
+        write_survey(&survey, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output.clone()).unwrap(),
+            "respondent_id,score,classification,timestamp,plan,region\n\
+             a,9,Promoter,2023-11-14T22:13:20Z,pro,EMEA\n\
+             \"b, inc\",4,Detractor,,,APAC\n"
+        );
+
+        let read_back = CsvImport::new("respondent_id", "score")
+            .timestamp_column("timestamp")
+            .attribute_columns(["plan", "region"])
+            .read(output.as_slice())
+            .unwrap();
+        let original: Vec<_> = survey.responses().collect();
+        let copied: Vec<_> = read_back.responses().collect();
+        assert_eq!(original, copied);
+
+        survey
+            .add_response_with_attributes("c".to_string(), 7, [("timestamp", "yesterday")])
+            .unwrap();
+        let error = write_survey(&survey, io::sink()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(
+            error.to_string(),
+            "attribute \"timestamp\" clashes with the column of the same name"
+        );
+    }
+
+    #[test]
+    fn test_comment_round_trip() {
+        let mut survey: Survey<String> = Survey::new();
+        survey
+            .add_response_with_comment("a".to_string(), 2, "Too slow, \"again\"")
+            .unwrap();
+        survey.add_response("b".to_string(), 10).unwrap();
+
+        let mut output = Vec::new();
+        write_survey(&survey, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output.clone()).unwrap(),
+            "respondent_id,score,classification,timestamp,comment\n\
+             a,2,Detractor,,\"Too slow, \"\"again\"\"\"\n\
+             b,10,Promoter,,\n"
+        );
+
+        let read_back = CsvImport::new("respondent_id", "score")
+            .comment_column("comment")
+            .read(output.as_slice())
+            .unwrap();
+        let original: Vec<_> = survey.responses().collect();
+        let copied: Vec<_> = read_back.responses().collect();
+        assert_eq!(original, copied);
+
+        survey
+            .add_response_with_attributes("c".to_string(), 7, [("comment", "none")])
+            .unwrap();
+        let error = write_survey(&survey, io::sink()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(
+            error.to_string(),
+            "attribute \"comment\" clashes with the column of the same name"
+        );
+    }
+}
@@ -0,0 +1,439 @@
+//! Employee Net Promoter Score (eNPS) surveys with anonymity safeguards.
+//!
+//! An [`EmployeeSurvey`] collects responses like a [`Survey`], but never keeps respondent IDs:
+//! they are either replaced by a keyed hash, so that repeat responses from the same employee are
+//! still recognised, or dropped entirely. Every score, segment or group-by query on fewer
+//! responses than the survey's minimum group size returns [`Reported::Suppressed`] instead of a
+//! number. Group-by queries also suppress the next-smallest groups when needed, so that a small
+//! group cannot be recovered by subtracting the shown groups from the overall result.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//!
+//! let mut survey = EmployeeSurvey::new(3, RespondentIds::Hashed);
+//! for (employee, score, team) in [
+//!     ("ana", 9, "platform"),
+//!     ("ben", 10, "platform"),
+//!     ("cy", 6, "platform"),
+//!     ("dee", 3, "design"),
+//!     ("eve", 8, "sales"),
+//!     ("fay", 10, "sales"),
+//!     ("gus", 2, "sales"),
+//! ] {
+//!     survey
+//!         .add_response_with_attributes(employee, score, [("team", team)])
+//!         .unwrap();
+//! }
+//!
+//! assert_eq!(survey.score(), Reported::Value(0));
+//! let teams = survey.group_by("team");
+//! assert_eq!(teams["design"], Reported::Suppressed);
+//! // Also withheld, otherwise the design team would be the overall counts minus the other teams.
+//! assert_eq!(teams["platform"], Reported::Suppressed);
+//! assert!(teams["sales"].value().is_some());
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::hash::{BuildHasher, Hash};
+
+use crate::metrics::Metric;
+use crate::scale::{ClassificationScheme, NpsScheme};
+use crate::stats::{ConfidenceInterval, IntervalMethod};
+use crate::{
+    NetPromoterScoreError, NpsRating, RoundingPolicy, ScoreCount, SegmentCounts, Survey,
+    SurveyResponse,
+};
+
+/// A result that is only shown when it is based on enough responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Reported<T> {
+    /// The result, computed from at least the minimum group size of responses.
+    Value(T),
+    /// The result was withheld because the group has fewer responses than the minimum group size.
+    Suppressed,
+}
+
+impl<T> Reported<T> {
+    /// Returns the result, or `None` if it was suppressed.
+    pub fn value(self) -> Option<T> {
+        match self {
+            Reported::Value(value) => Some(value),
+            Reported::Suppressed => None,
+        }
+    }
+
+    /// Returns `true` if the result was suppressed.
+    pub fn is_suppressed(&self) -> bool {
+        matches!(self, Reported::Suppressed)
+    }
+
+    /// Applies `f` to the result, leaving a suppressed result suppressed.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Reported<U> {
+        match self {
+            Reported::Value(value) => Reported::Value(f(value)),
+            Reported::Suppressed => Reported::Suppressed,
+        }
+    }
+}
+
+impl<T: Display> Display for Reported<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Reported::Value(value) => value.fmt(f),
+            Reported::Suppressed => write!(f, "suppressed"),
+        }
+    }
+}
+
+/// How an [`EmployeeSurvey`] treats respondent IDs at ingestion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RespondentIds {
+    /// Replaces each ID with a hash keyed randomly for each survey, so repeat responses from the
+    /// same respondent are handled by the [`DuplicatePolicy`](crate::DuplicatePolicy) but the
+    /// original IDs cannot be recovered.
+    #[default]
+    Hashed,
+    /// Discards each ID and treats every response as coming from a new respondent.
+    Dropped,
+}
+
+/// An employee survey that never stores respondent IDs and withholds results for small groups.
+pub struct EmployeeSurvey<S = NpsScheme> {
+    survey: Survey<u64, S>,
+    min_group_size: ScoreCount,
+    respondent_ids: RespondentIds,
+    hasher: RandomState,
+    next_id: u64,
+}
+
+impl EmployeeSurvey {
+    /// Creates a new empty eNPS survey using the standard [`NpsScheme`] that suppresses results
+    /// for groups of fewer than `min_group_size` responses.
+    pub fn new(min_group_size: ScoreCount, respondent_ids: RespondentIds) -> Self {
+        Self::with_scheme(NpsScheme, min_group_size, respondent_ids)
+    }
+}
+
+impl<S: ClassificationScheme> EmployeeSurvey<S> {
+    /// Creates a new empty eNPS survey that validates and classifies ratings with `scheme` and
+    /// suppresses results for groups of fewer than `min_group_size` responses.
+    pub fn with_scheme(
+        scheme: S,
+        min_group_size: ScoreCount,
+        respondent_ids: RespondentIds,
+    ) -> Self {
+        Self {
+            survey: Survey::with_scheme(scheme),
+            min_group_size,
+            respondent_ids,
+            hasher: RandomState::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Returns the smallest number of responses for which results are shown.
+    pub fn min_group_size(&self) -> ScoreCount {
+        self.min_group_size
+    }
+
+    /// Returns the `RoundingPolicy` used by [`score`](EmployeeSurvey::score).
+    pub fn rounding_policy(&self) -> RoundingPolicy {
+        self.survey.rounding_policy()
+    }
+
+    /// Sets the `RoundingPolicy` used by [`score`](EmployeeSurvey::score).
+    pub fn set_rounding_policy(&mut self, policy: RoundingPolicy) {
+        self.survey.set_rounding_policy(policy);
+    }
+
+    /// Returns how respondent IDs are treated at ingestion.
+    pub fn respondent_ids(&self) -> RespondentIds {
+        self.respondent_ids
+    }
+
+    /// Adds a response with the given respondent ID and score. The ID is hashed or dropped
+    /// before the response is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `score` is not a valid rating on the survey's scale.
+    pub fn add_response(
+        &mut self,
+        respondent_id: impl Hash,
+        score: NpsRating,
+    ) -> Result<(), NetPromoterScoreError> {
+        self.add_response_with_attributes(respondent_id, score, Vec::<(String, String)>::new())
+    }
+
+    /// Adds a response with the given respondent ID, score and attributes, such as team or
+    /// location. The ID is hashed or dropped before the response is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `score` is not a valid rating on the survey's scale.
+    pub fn add_response_with_attributes<K, V>(
+        &mut self,
+        respondent_id: impl Hash,
+        score: NpsRating,
+        attributes: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<(), NetPromoterScoreError>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let id = self.anonymize(respondent_id);
+        let response = SurveyResponse::new(id, score)?.with_attributes(attributes);
+        self.survey.add_survey_response(response).map(|_| ())
+    }
+
+    /// Adds multiple responses, returning the errors of any that could not be added.
+    pub fn add_multiple_responses<I: Hash>(
+        &mut self,
+        responses: impl IntoIterator<Item = (I, NpsRating)>,
+    ) -> Result<(), Vec<NetPromoterScoreError>> {
+        let errors: Vec<_> = responses
+            .into_iter()
+            .filter_map(|(respondent_id, score)| self.add_response(respondent_id, score).err())
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns the eNPS of the survey, rounded with the survey's
+    /// [`rounding_policy`](EmployeeSurvey::rounding_policy), unless there are too few responses.
+    pub fn score(&self) -> Reported<i32> {
+        self.score_exact()
+            .map(|score| self.survey.rounding_policy().round(score) as i32)
+    }
+
+    /// Returns the exact, unrounded eNPS of the survey, unless there are too few responses.
+    pub fn score_exact(&self) -> Reported<f64> {
+        self.segment_counts().map(|counts| counts.score_exact())
+    }
+
+    /// Returns the number of detractors, passives and promoters, unless there are too few
+    /// responses.
+    pub fn segment_counts(&self) -> Reported<SegmentCounts> {
+        self.suppress(self.survey.segment_counts())
+    }
+
+    /// Returns a confidence interval for the eNPS of the survey, unless there are too few
+    /// responses.
+    ///
+    /// # Errors
+    ///
+    /// See [`SegmentCounts::confidence_interval`].
+    pub fn confidence_interval(
+        &self,
+        level: f64,
+        method: IntervalMethod,
+    ) -> Result<Reported<ConfidenceInterval>, NetPromoterScoreError> {
+        match self.segment_counts() {
+            Reported::Value(counts) => counts
+                .confidence_interval(level, method)
+                .map(Reported::Value),
+            Reported::Suppressed => Ok(Reported::Suppressed),
+        }
+    }
+
+    /// Evaluates a [`Metric`] against the survey, unless there are too few responses.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error raised by the metric.
+    pub fn evaluate<M: Metric + ?Sized>(
+        &self,
+        metric: &M,
+    ) -> Result<Reported<f64>, NetPromoterScoreError> {
+        if self.survey.len() < self.min_group_size {
+            return Ok(Reported::Suppressed);
+        }
+        self.survey.evaluate(metric).map(Reported::Value)
+    }
+
+    /// Groups the responses by the value of the attribute `key`, suppressing the segment counts
+    /// of every group with too few responses.
+    ///
+    /// If the suppressed groups, together with the responses without the attribute, hold fewer
+    /// than the minimum group size of responses, the next-smallest groups are suppressed too, so
+    /// that subtracting the shown groups from [`segment_counts`](Self::segment_counts) never
+    /// reveals a small group.
+    pub fn group_by(&self, key: &str) -> BTreeMap<String, Reported<SegmentCounts>> {
+        self.suppress_groups(self.survey.group_by(key))
+    }
+
+    /// Groups the responses by the values of several attributes, suppressing the segment counts
+    /// of every group with too few responses and the next-smallest groups as
+    /// [`group_by`](Self::group_by) does.
+    pub fn group_by_keys(&self, keys: &[&str]) -> BTreeMap<Vec<String>, Reported<SegmentCounts>> {
+        self.suppress_groups(self.survey.group_by_keys(keys))
+    }
+
+    fn anonymize(&mut self, respondent_id: impl Hash) -> u64 {
+        match self.respondent_ids {
+            RespondentIds::Hashed => self.hasher.hash_one(respondent_id),
+            RespondentIds::Dropped => {
+                self.next_id += 1;
+                self.next_id
+            }
+        }
+    }
+
+    fn suppress(&self, counts: SegmentCounts) -> Reported<SegmentCounts> {
+        if counts.total() < self.min_group_size {
+            Reported::Suppressed
+        } else {
+            Reported::Value(counts)
+        }
+    }
+
+    fn suppress_groups<K: Ord>(
+        &self,
+        groups: BTreeMap<K, SegmentCounts>,
+    ) -> BTreeMap<K, Reported<SegmentCounts>> {
+        let groups: Vec<(K, SegmentCounts)> = groups.into_iter().collect();
+        // Responses outside every group can be recovered by subtraction, so they count as hidden.
+        let grouped: ScoreCount = groups.iter().map(|(_, counts)| counts.total()).sum();
+        let mut hidden = self.survey.len() - grouped;
+        let mut by_size: Vec<usize> = (0..groups.len()).collect();
+        by_size.sort_by_key(|&index| groups[index].1.total());
+
+        let mut suppressed = vec![false; groups.len()];
+        for index in by_size {
+            let size = groups[index].1.total();
+            let exposed = hidden > 0 && hidden < self.min_group_size;
+            if size >= self.min_group_size && !exposed {
+                break;
+            }
+            hidden += size;
+            suppressed[index] = true;
+        }
+        groups
+            .into_iter()
+            .zip(suppressed)
+            .map(|((group, counts), suppressed)| {
+                let reported = if suppressed {
+                    Reported::Suppressed
+                } else {
+                    Reported::Value(counts)
+                };
+                (group, reported)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::Mean;
+
+    #[test]
+    fn test_small_groups_are_suppressed() {
+        let mut survey = EmployeeSurvey::new(2, RespondentIds::Hashed);
+        survey
+            .add_response_with_attributes("ana", 10, [("team", "a")])
+            .unwrap();
+        assert_eq!(survey.score(), Reported::Suppressed);
+        assert_eq!(survey.segment_counts(), Reported::Suppressed);
+        assert_eq!(survey.evaluate(&Mean), Ok(Reported::Suppressed));
+        assert_eq!(
+            survey.confidence_interval(0.95, IntervalMethod::Trinomial),
+            Ok(Reported::Suppressed)
+        );
+
+        survey
+            .add_response_with_attributes("ben", 0, [("team", "b")])
+            .unwrap();
+        assert_eq!(survey.score(), Reported::Value(0));
+        assert_eq!(survey.evaluate(&Mean), Ok(Reported::Value(5.0)));
+        assert!(survey
+            .group_by("team")
+            .values()
+            .all(Reported::is_suppressed));
+        assert_eq!(survey.score().to_string(), "0");
+        assert_eq!(Reported::<i32>::Suppressed.to_string(), "suppressed");
+    }
+
+    #[test]
+    fn test_rounding_policy() {
+        let mut survey = EmployeeSurvey::new(1, RespondentIds::Dropped);
+        let ratings = [10, 10, 0, 8, 8, 8, 8, 8];
+        survey
+            .add_multiple_responses(ratings.iter().map(|&rating| ((), rating)))
+            .unwrap();
+        assert_eq!(survey.score_exact(), Reported::Value(12.5));
+        assert_eq!(survey.rounding_policy(), RoundingPolicy::HalfUp);
+        assert_eq!(survey.score(), Reported::Value(13));
+
+        survey.set_rounding_policy(RoundingPolicy::HalfEven);
+        assert_eq!(survey.rounding_policy(), RoundingPolicy::HalfEven);
+        assert_eq!(survey.score(), Reported::Value(12));
+    }
+
+    #[test]
+    fn test_respondent_ids() {
+        let mut hashed = EmployeeSurvey::new(1, RespondentIds::Hashed);
+        hashed
+            .add_multiple_responses(vec![("ana", 0), ("ana", 10)])
+            .unwrap();
+        assert_eq!(hashed.score(), Reported::Value(100));
+
+        let mut dropped = EmployeeSurvey::new(1, RespondentIds::Dropped);
+        dropped
+            .add_multiple_responses(vec![("ana", 0), ("ana", 10)])
+            .unwrap();
+        assert_eq!(dropped.score(), Reported::Value(0));
+    }
+
+    #[test]
+    fn test_suppressed_groups_cannot_be_recovered_by_subtraction() {
+        let mut survey = EmployeeSurvey::new(3, RespondentIds::Dropped);
+        for (score, team) in [(9, "a"), (10, "a"), (6, "a"), (3, "b"), (8, "c"), (10, "c")] {
+            survey
+                .add_response_with_attributes((), score, [("team", team)])
+                .unwrap();
+        }
+        survey.add_response((), 10).unwrap();
+        survey.add_response((), 0).unwrap();
+
+        let total = survey.segment_counts().value().unwrap();
+        let teams = survey.group_by("team");
+        assert_eq!(teams["b"], Reported::Suppressed);
+        assert_eq!(teams["c"], Reported::Suppressed);
+        assert!(teams["a"].value().is_some());
+
+        let shown = teams.values().filter_map(|team| team.value());
+        let hidden = shown.fold(total.total(), |hidden, counts| hidden - counts.total());
+        assert!(hidden >= survey.min_group_size());
+
+        let mut by_site = EmployeeSurvey::new(2, RespondentIds::Dropped);
+        for (score, site) in [(9, "x"), (9, "x"), (0, "y"), (0, "y")] {
+            by_site
+                .add_response_with_attributes((), score, [("site", site)])
+                .unwrap();
+        }
+        by_site.add_response((), 7).unwrap();
+        let sites = by_site.group_by_keys(&["site"]);
+        assert_eq!(sites[&vec!["x".to_string()]], Reported::Suppressed);
+        assert!(sites[&vec!["y".to_string()]].value().is_some());
+    }
+
+    #[test]
+    fn test_invalid_ratings() {
+        let mut survey = EmployeeSurvey::new(1, RespondentIds::Dropped);
+        assert_eq!(
+            survey.add_multiple_responses(vec![(1, 11), (2, 9)]),
+            Err(vec![NetPromoterScoreError::InvalidRating(11)])
+        );
+        assert_eq!(survey.score(), Reported::Value(100));
+    }
+}
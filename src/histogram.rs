@@ -0,0 +1,197 @@
+//! Constant-time scoring from per-rating counts.
+//!
+//! A [`ScoreHistogram`] keeps one counter for each rating from 0 to 10, so it can be updated in
+//! constant time as responses are inserted, replaced or removed, and it computes the Net Promoter
+//! Score without looking at individual responses. Every [`Survey`](crate::Survey) maintains one
+//! internally; it can also be used on its own when respondent IDs don't need to be kept.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//! use std::convert::TryFrom;
+//!
+//! let mut histogram = ScoreHistogram::new();
+//! for rating in [10, 9, 8, 3] {
+//!     histogram.insert(Rating::try_from(rating).unwrap());
+//! }
+//! assert_eq!(histogram.total(), 4);
+//! assert_eq!(histogram.score_exact(), 25.0);
+//! ```
+
+use std::ops::{Add, AddAssign};
+
+use crate::scale::{ClassificationScheme, NpsScheme};
+use crate::{Classification, NpsRating, Rating, ScoreCount, SegmentCounts};
+
+/// The number of responses for each rating from 0 to 10.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreHistogram {
+    counts: [ScoreCount; 11],
+}
+
+impl ScoreHistogram {
+    /// Creates an empty histogram.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a histogram from the number of responses for each rating, indexed by rating.
+    pub fn from_counts(counts: [ScoreCount; 11]) -> Self {
+        Self { counts }
+    }
+
+    /// Counts one more response with the given rating.
+    pub fn insert(&mut self, rating: Rating) {
+        self.counts[*rating as usize] += 1;
+    }
+
+    /// Counts one less response with the given rating.
+    ///
+    /// Returns `false`, leaving the histogram unchanged, if there was no response with that rating.
+    pub fn remove(&mut self, rating: Rating) -> bool {
+        let count = &mut self.counts[*rating as usize];
+        if *count == 0 {
+            return false;
+        }
+        *count -= 1;
+        true
+    }
+
+    /// Moves one response from the `old` rating to the `new` rating.
+    ///
+    /// Returns `false`, leaving the histogram unchanged, if there was no response with the `old`
+    /// rating.
+    pub fn replace(&mut self, old: Rating, new: Rating) -> bool {
+        let removed = self.remove(old);
+        if removed {
+            self.insert(new);
+        }
+        removed
+    }
+
+    /// Returns the number of responses with the given rating, or 0 for ratings above 10.
+    pub fn count(&self, rating: NpsRating) -> ScoreCount {
+        self.counts.get(rating as usize).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of responses for each rating, indexed by rating.
+    pub fn counts(&self) -> &[ScoreCount; 11] {
+        &self.counts
+    }
+
+    /// Returns the total number of responses.
+    pub fn total(&self) -> ScoreCount {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the number of detractors, passives and promoters under the standard NPS scheme.
+    pub fn segment_counts(&self) -> SegmentCounts {
+        self.segment_counts_with(&NpsScheme)
+    }
+
+    /// Returns the number of detractors, passives and promoters, classifying each rating with
+    /// `scheme`.
+    pub fn segment_counts_with(&self, scheme: &impl ClassificationScheme) -> SegmentCounts {
+        self.counts.iter().enumerate().fold(
+            SegmentCounts::default(),
+            |mut segments, (rating, &count)| {
+                if count == 0 {
+                    return segments;
+                }
+                let segment = match scheme.classify(Rating(rating as NpsRating)) {
+                    Classification::Detractor => &mut segments.detractors,
+                    Classification::Passive => &mut segments.passives,
+                    Classification::Promoter => &mut segments.promoters,
+                };
+                *segment += count;
+                segments
+            },
+        )
+    }
+
+    /// Returns the exact Net Promoter Score under the standard NPS scheme, or `0.0` if the
+    /// histogram is empty.
+    pub fn score_exact(&self) -> f64 {
+        self.segment_counts().score_exact()
+    }
+}
+
+impl Add for ScoreHistogram {
+    type Output = ScoreHistogram;
+
+    fn add(mut self, other: ScoreHistogram) -> ScoreHistogram {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign for ScoreHistogram {
+    fn add_assign(&mut self, other: ScoreHistogram) {
+        for (count, other) in self.counts.iter_mut().zip(other.counts) {
+            *count += other;
+        }
+    }
+}
+
+impl Extend<Rating> for ScoreHistogram {
+    fn extend<I: IntoIterator<Item = Rating>>(&mut self, iter: I) {
+        for rating in iter {
+            self.insert(rating);
+        }
+    }
+}
+
+impl FromIterator<Rating> for ScoreHistogram {
+    fn from_iter<I: IntoIterator<Item = Rating>>(iter: I) -> Self {
+        let mut histogram = ScoreHistogram::new();
+        histogram.extend(iter);
+        histogram
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove_replace() {
+        let mut histogram = ScoreHistogram::new();
+        histogram.insert(Rating(10));
+        histogram.insert(Rating(10));
+        histogram.insert(Rating(6));
+        assert_eq!(histogram.count(10), 2);
+        assert_eq!(histogram.total(), 3);
+
+        assert!(histogram.replace(Rating(6), Rating(8)));
+        assert_eq!(histogram.count(6), 0);
+        assert_eq!(histogram.count(8), 1);
+
+        assert!(histogram.remove(Rating(10)));
+        assert!(!histogram.remove(Rating(0)));
+        assert!(!histogram.replace(Rating(0), Rating(10)));
+        assert_eq!(histogram.counts(), &[0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 1]);
+        assert_eq!(histogram.count(11), 0);
+    }
+
+    #[test]
+    fn test_segment_counts_and_score() {
+        let histogram: ScoreHistogram =
+            [0, 3, 6, 7, 8, 9, 10, 10].into_iter().map(Rating).collect();
+        assert_eq!(
+            histogram.segment_counts(),
+            SegmentCounts {
+                detractors: 3,
+                passives: 2,
+                promoters: 3
+            }
+        );
+        assert_eq!(histogram.score_exact(), 0.0);
+        assert_eq!(ScoreHistogram::new().score_exact(), 0.0);
+
+        let doubled = histogram + histogram;
+        assert_eq!(doubled.total(), 16);
+        assert_eq!(doubled.count(10), 4);
+    }
+}
@@ -0,0 +1,386 @@
+//! Batch ingestion with per-entry reporting.
+//!
+//! [`Survey::ingest`](crate::Survey::ingest) adds a batch of entries and returns an
+//! [`IngestReport`] listing how many were accepted, which were rejected with their position,
+//! respondent ID and reason, which respondents collided with an existing response, and what
+//! happened to those duplicates. [`Survey::ingest_transactional`](crate::Survey::ingest_transactional)
+//! adds the batch only if every entry would be accepted.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//!
+//! let mut survey = Survey::new();
+//! let report = survey.ingest(vec![("ana", 10), ("ben", 42), ("cy", 3), ("ana", 9)]);
+//!
+//! assert_eq!(report.accepted, 3);
+//! assert_eq!(report.rejected[0].index, 1);
+//! assert_eq!(report.rejected[0].respondent_id, "ben");
+//! assert_eq!(report.rejected[0].to_string(), "entry 1 (respondent ben): Invalid rating value: 42 (expected 0 to 10)");
+//! assert_eq!(report.duplicates, vec!["ana"]);
+//! assert_eq!(survey.len(), 2);
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+use std::time::SystemTime;
+
+use crate::scale::ClassificationScheme;
+use crate::{
+    BatchError, DuplicatePolicy, NetPromoterScoreError, NpsRating, RejectedEntry, Survey,
+    SurveyResponse,
+};
+
+/// An entry that can be ingested into a [`Survey`]: a `(respondent_id, score)` pair, a
+/// `(respondent_id, score, timestamp)` triple or a [`SurveyResponse`].
+pub trait IngestEntry<T> {
+    /// Returns the respondent ID and the response, or the reason the response is invalid.
+    fn into_response(self) -> (T, Result<SurveyResponse<T>, NetPromoterScoreError>);
+}
+
+impl<T: PartialEq + Clone> IngestEntry<T> for (T, NpsRating) {
+    fn into_response(self) -> (T, Result<SurveyResponse<T>, NetPromoterScoreError>) {
+        let (respondent_id, score) = self;
+        let response = SurveyResponse::new(respondent_id.clone(), score);
+        (respondent_id, response)
+    }
+}
+
+impl<T: PartialEq + Clone> IngestEntry<T> for (T, NpsRating, SystemTime) {
+    fn into_response(self) -> (T, Result<SurveyResponse<T>, NetPromoterScoreError>) {
+        let (respondent_id, score, timestamp) = self;
+        let response = SurveyResponse::new_at(respondent_id.clone(), score, timestamp);
+        (respondent_id, response)
+    }
+}
+
+impl<T: PartialEq + Clone> IngestEntry<T> for SurveyResponse<T> {
+    fn into_response(self) -> (T, Result<SurveyResponse<T>, NetPromoterScoreError>) {
+        (self.respondent_id().clone(), Ok(self))
+    }
+}
+
+/// The outcome of ingesting a batch of entries into a [`Survey`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IngestReport<T> {
+    /// The number of entries added to the survey, including duplicates that were handled by the
+    /// survey's [`DuplicatePolicy`] without an error.
+    pub accepted: usize,
+    /// The entries that were not added, in input order.
+    pub rejected: Vec<RejectedEntry<T>>,
+    /// The IDs of accepted respondents that collided with an existing response, in input order.
+    pub duplicates: Vec<T>,
+    /// Notes on accepted entries that did not simply add a new response, in input order.
+    pub warnings: Vec<IngestWarning<T>>,
+}
+
+impl<T> IngestReport<T> {
+    fn new() -> Self {
+        Self {
+            accepted: 0,
+            rejected: Vec::new(),
+            duplicates: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if no entry was rejected.
+    pub fn is_complete(&self) -> bool {
+        self.rejected.is_empty()
+    }
+
+    /// Returns the number of entries in the batch.
+    pub fn total(&self) -> usize {
+        self.accepted + self.rejected.len()
+    }
+
+    /// Returns the IDs of the duplicate respondents if no entry was rejected, or a [`BatchError`]
+    /// with the rejected entries and the duplicates otherwise, as the batch methods of [`Survey`]
+    /// do. The warnings are dropped.
+    pub fn into_result(self) -> Result<Vec<T>, BatchError<T>> {
+        if self.is_complete() {
+            Ok(self.duplicates)
+        } else {
+            Err(BatchError {
+                rejected: self.rejected,
+                duplicates: self.duplicates,
+            })
+        }
+    }
+}
+
+/// A note on an accepted entry whose respondent already had a response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IngestWarning<T> {
+    /// The entry was ignored because the respondent's first response is kept, under
+    /// [`DuplicatePolicy::KeepFirst`].
+    DuplicateIgnored {
+        /// The 0-based position of the entry in the batch.
+        index: usize,
+        /// The respondent ID of the entry.
+        respondent_id: T,
+    },
+    /// The entry replaced the respondent's earlier response, under [`DuplicatePolicy::KeepLast`]
+    /// or [`DuplicatePolicy::KeepAll`].
+    DuplicateReplaced {
+        /// The 0-based position of the entry in the batch.
+        index: usize,
+        /// The respondent ID of the entry.
+        respondent_id: T,
+    },
+}
+
+impl<T: Display> Display for IngestWarning<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            IngestWarning::DuplicateIgnored {
+                index,
+                respondent_id,
+            } => write!(
+                f,
+                "entry {} (respondent {}): ignored duplicate response",
+                index, respondent_id
+            ),
+            IngestWarning::DuplicateReplaced {
+                index,
+                respondent_id,
+            } => write!(
+                f,
+                "entry {} (respondent {}): replaced earlier response",
+                index, respondent_id
+            ),
+        }
+    }
+}
+
+impl<T: PartialEq + Ord + Clone, S: ClassificationScheme> Survey<T, S> {
+    /// Adds a batch of entries, keeping the valid ones, and reports what happened to each.
+    ///
+    /// Entries can be `(respondent_id, score)` pairs, `(respondent_id, score, timestamp)` triples
+    /// or [`SurveyResponse`] values. Duplicate respondents are handled according to the survey's
+    /// [`DuplicatePolicy`]; under [`DuplicatePolicy::Reject`] they are listed as rejected.
+    pub fn ingest<E: IngestEntry<T>>(
+        &mut self,
+        entries: impl IntoIterator<Item = E>,
+    ) -> IngestReport<T> {
+        let mut report = IngestReport::new();
+        for (index, entry) in entries.into_iter().enumerate() {
+            let (respondent_id, response) = entry.into_response();
+            let result =
+                response.and_then(|response| self.insert_response(respondent_id.clone(), response));
+            match result {
+                Ok(None) => report.accepted += 1,
+                Ok(Some(duplicate)) => {
+                    report.accepted += 1;
+                    report.warnings.push(match self.duplicate_policy {
+                        DuplicatePolicy::KeepFirst => IngestWarning::DuplicateIgnored {
+                            index,
+                            respondent_id,
+                        },
+                        _ => IngestWarning::DuplicateReplaced {
+                            index,
+                            respondent_id,
+                        },
+                    });
+                    report.duplicates.push(duplicate);
+                }
+                Err(reason) => report.rejected.push(RejectedEntry {
+                    index,
+                    respondent_id,
+                    reason,
+                }),
+            }
+        }
+        report
+    }
+
+    /// Adds a batch of entries only if every one of them would be accepted.
+    ///
+    /// Returns the report of the ingestion on success. If any entry would be rejected, the survey
+    /// is left untouched and a report listing every rejected entry, with nothing accepted, is
+    /// returned as the error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.set_duplicate_policy(DuplicatePolicy::Reject);
+    /// survey.add_response(1, 10).unwrap();
+    ///
+    /// let report = survey
+    ///     .ingest_transactional(vec![(2, 9), (1, 0), (3, 11)])
+    ///     .unwrap_err();
+    /// assert_eq!(report.rejected.len(), 2);
+    /// assert_eq!(report.accepted, 0);
+    /// assert_eq!(survey.len(), 1);
+    ///
+    /// assert!(survey.ingest_transactional(vec![(2, 9), (3, 8)]).is_ok());
+    /// assert_eq!(survey.len(), 3);
+    /// ```
+    pub fn ingest_transactional<E: IngestEntry<T>>(
+        &mut self,
+        entries: impl IntoIterator<Item = E>,
+    ) -> Result<IngestReport<T>, IngestReport<T>> {
+        let responses: Vec<_> = entries
+            .into_iter()
+            .map(IngestEntry::into_response)
+            .collect();
+
+        // Check every entry without changing the survey, counting the valid earlier entries of the
+        // batch as existing responses.
+        let mut rejected = IngestReport::new();
+        let mut added = BTreeSet::new();
+        for (index, (respondent_id, response)) in responses.iter().enumerate() {
+            let duplicate =
+                self.responses.contains_key(respondent_id) || added.contains(respondent_id);
+            let check = response
+                .as_ref()
+                .map_err(Clone::clone)
+                .and_then(|response| self.validate_response(response, duplicate));
+            match check {
+                Ok(()) => {
+                    added.insert(respondent_id.clone());
+                }
+                Err(reason) => rejected.rejected.push(RejectedEntry {
+                    index,
+                    respondent_id: respondent_id.clone(),
+                    reason,
+                }),
+            }
+        }
+        if !rejected.is_complete() {
+            return Err(rejected);
+        }
+
+        let report = self.ingest(
+            responses
+                .into_iter()
+                .filter_map(|(_, response)| response.ok()),
+        );
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::Scale;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_ingest_report() {
+        let mut survey = Survey::with_scheme(Scale::new(1, 5, 4, 5).unwrap());
+        survey.set_duplicate_policy(DuplicatePolicy::KeepFirst);
+        survey.add_response(7, 5).unwrap();
+
+        let report = survey.ingest(vec![(1, 5), (2, 0), (7, 1), (3, 11), (1, 2)]);
+        assert_eq!(report.accepted, 3);
+        assert_eq!(report.total(), 5);
+        assert!(!report.is_complete());
+        assert_eq!(
+            report.rejected,
+            vec![
+                RejectedEntry {
+                    index: 1,
+                    respondent_id: 2,
+                    reason: NetPromoterScoreError::RatingOutOfRange {
+                        rating: 0,
+                        min: 1,
+                        max: 5
+                    }
+                },
+                RejectedEntry {
+                    index: 3,
+                    respondent_id: 3,
+                    reason: NetPromoterScoreError::InvalidRating(11)
+                },
+            ]
+        );
+        assert_eq!(report.duplicates, vec![7, 1]);
+        assert_eq!(
+            report.warnings[1],
+            IngestWarning::DuplicateIgnored {
+                index: 4,
+                respondent_id: 1
+            }
+        );
+        assert_eq!(
+            report.warnings[0].to_string(),
+            "entry 2 (respondent 7): ignored duplicate response"
+        );
+        assert_eq!(survey.score(), 100);
+    }
+
+    #[test]
+    fn test_into_result() {
+        let mut survey = Survey::new();
+        survey.add_response(1, 10).unwrap();
+        let report = survey.ingest(vec![(1, 9), (2, 11)]);
+        let error = report.clone().into_result().unwrap_err();
+        assert_eq!(error.rejected, report.rejected);
+        assert_eq!(error.duplicates, vec![1]);
+
+        let report = survey.ingest(vec![(2, 9), (1, 0)]);
+        assert_eq!(report.into_result(), Ok(vec![1]));
+    }
+
+    #[test]
+    fn test_ingest_entry_kinds() {
+        let mut survey = Survey::new();
+        let report = survey.ingest(vec![("a", 10, UNIX_EPOCH), ("a", 0, UNIX_EPOCH)]);
+        assert_eq!(
+            report.warnings,
+            vec![IngestWarning::DuplicateReplaced {
+                index: 1,
+                respondent_id: "a"
+            }]
+        );
+
+        let response = SurveyResponse::new("b", 9)
+            .unwrap()
+            .with_attribute("plan", "pro");
+        assert!(survey.ingest(vec![response]).is_complete());
+        assert_eq!(survey.group_by("plan")["pro"].promoters, 1);
+        assert_eq!(survey.score(), 0);
+    }
+
+    #[test]
+    fn test_transactional_ingest_leaves_survey_untouched() {
+        let mut survey = Survey::new();
+        survey.set_duplicate_policy(DuplicatePolicy::Reject);
+        survey.add_response(1, 10).unwrap();
+
+        // A duplicate within the batch is caught before anything is added.
+        let report = survey
+            .ingest_transactional(vec![(2, 0), (3, 0), (2, 5)])
+            .unwrap_err();
+        assert_eq!(report.rejected[0].index, 2);
+        assert_eq!(
+            report.rejected[0].reason,
+            NetPromoterScoreError::DuplicateRespondent
+        );
+        assert_eq!(survey.len(), 1);
+        assert_eq!(survey.score(), 100);
+
+        // An invalid entry does not make a later one for the same respondent a duplicate.
+        let report = survey
+            .ingest_transactional(vec![(4, 12), (4, 9)])
+            .unwrap_err();
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(
+            report.rejected[0].reason,
+            NetPromoterScoreError::InvalidRating(12)
+        );
+
+        survey.set_duplicate_policy(DuplicatePolicy::KeepLast);
+        let report = survey.ingest_transactional(vec![(2, 0), (1, 0)]).unwrap();
+        assert_eq!(report.accepted, 2);
+        assert_eq!(report.duplicates, vec![1]);
+        assert_eq!(survey.score(), -100);
+    }
+}
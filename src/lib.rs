@@ -50,7 +50,7 @@
 //! use net_promoter_score::prelude::*;
 //! use anyhow::Result;
 //!
-//! fn main() -> Result<(), Vec<NetPromoterScoreError>> {
+//! fn main() -> Result<(), BatchError<i32>> {
 //!     let mut survey = Survey::new();
 //!
 //!     survey.add_multiple_responses(vec![(1, 9), (2, 8), (3, 6)])?;
@@ -68,7 +68,7 @@
 //! use net_promoter_score::prelude::*;
 //! use anyhow::Result;
 //!
-//! fn main() -> Result<(), Vec<NetPromoterScoreError>> {
+//! fn main() -> Result<(), BatchError<i32>> {
 //!     let rating_quantities: &[(NpsRating, ScoreCount)] = &[
 //!         (1, 2),
 //!         (4, 1),
@@ -117,7 +117,7 @@
 //! use net_promoter_score::prelude::*;
 //! use anyhow::Result;
 //!
-//! fn main() -> Result<(), Vec<NetPromoterScoreError>> {
+//! fn main() -> Result<(), BatchError<String>> {
 //!     let rating_quantities: &[(NpsRating, ScoreCount)] = &[
 //!         (1, 2),
 //!         (4, 1),
@@ -168,16 +168,52 @@
 //! 🐦 Follow   <https://twitter.com/rrrodzilla>
 //!
 
+pub mod aggregate;
+pub mod concurrent;
+#[cfg(feature = "csv")]
+pub mod csv;
+pub mod enps;
+pub mod histogram;
+pub mod ingest;
+pub mod metrics;
+pub mod migration;
+pub mod panel;
 pub mod prelude;
+pub mod scale;
+pub mod sentiment;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod stats;
+pub mod summary;
+pub mod text;
+#[cfg(feature = "themes")]
+pub mod themes;
+pub mod timeseries;
+use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::fmt::{self, Display, Formatter};
 use std::iter::{Extend, FromIterator};
-use std::ops::Deref;
+use std::ops::{Add, AddAssign, Deref, RangeBounds};
+use std::sync::OnceLock;
+use std::time::SystemTime;
+
+use aggregate::NpsAggregate;
+use histogram::ScoreHistogram;
+use metrics::Metric;
+use scale::{ClassificationScheme, NpsScheme};
+use stats::{ConfidenceInterval, IntervalMethod};
+use summary::NpsSummary;
+use timeseries::{Period, TimeSeries};
 
 /// A `Survey` represents a collection of survey responses, where each response
 /// includes a respondent's ID of type `T` and a score in the range of 0 to 10.
-/// Responses are stored in a BTreeMap, which maintains the responses in order.
+/// Responses are stored in a BTreeMap, which maintains the responses in order, alongside a
+/// [`ScoreHistogram`] of rating counts that is updated on every insert so that scoring never has
+/// to scan the responses.
+///
+/// Ratings are validated and classified by a [`ClassificationScheme`] of type `S`, which defaults
+/// to the standard [`NpsScheme`]. Use [`Survey::with_scheme`] for other scales or thresholds.
 ///
 /// The primary purpose of the [net_promoter_score](crate) crate is to calculate the Net
 /// Promoter Rating (NPS) based on the gathered responses.
@@ -201,9 +237,15 @@ use std::ops::Deref;
 ///     Ok(())
 /// }
 /// ```
-pub struct Survey<T> {
+#[derive(Clone)]
+pub struct Survey<T, S = NpsScheme> {
     responses: BTreeMap<T, SurveyResponse<T>>,
-    nps_cache: Option<i32>,
+    history: BTreeMap<T, Vec<SurveyResponse<T>>>,
+    histogram: ScoreHistogram,
+    nps_cache: OnceLock<f64>,
+    rounding_policy: RoundingPolicy,
+    duplicate_policy: DuplicatePolicy,
+    scheme: S,
 }
 
 /// Represents the count or frequency of a particular score in a Net Promoter Score (NPS) survey.
@@ -247,26 +289,77 @@ pub type ScoreCount = usize;
 pub type NpsRating = u8;
 
 impl<T: PartialEq + Ord + Clone> Survey<T> {
-    /// Creates a new empty survey.
+    /// Creates a new empty survey using the standard [`NpsScheme`].
     pub fn new() -> Self {
         Default::default()
     }
 
-    fn calculate_nps(&mut self) {
-        let total_responses = self.responses.len() as i32;
-        if total_responses == 0 {
-            self.nps_cache = Some(0);
-            return;
+    /// Creates a new survey from a given set of responses.
+    ///
+    /// If any of the responses have an invalid rating, an error will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use net_promoter_score::prelude::*;
+    /// # use anyhow::Error;
+    /// let survey_result: Result<Survey<u32>, Vec<Error>> =
+    ///     Survey::<u32>::from_responses(vec![(1, 10), (2, 9), (3, 9), (4, 8), (5, 7), (6, 6)]);
+    /// ```
+    pub fn from_responses<E>(
+        responses: impl IntoIterator<Item = (T, NpsRating)>,
+    ) -> Result<Self, Vec<E>>
+    where
+        E: From<NetPromoterScoreError>,
+    {
+        let mut survey = Self::new();
+        match survey.add_multiple_responses(responses) {
+            Ok(_) => Ok(survey),
+            Err(error) => Err(error
+                .rejected
+                .into_iter()
+                .map(|entry| E::from(entry.reason))
+                .collect()),
         }
+    }
+}
 
-        let promoters = self.segment(Classification::Promoter).len() as i32;
-        let detractors = self.segment(Classification::Detractor).len() as i32;
+impl<T: PartialEq + Ord + Clone, S: ClassificationScheme> Survey<T, S> {
+    /// Creates a new empty survey that validates and classifies ratings with `scheme`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// // Legacy surveys on a 1 to 10 scale
+    /// let mut survey = Survey::with_scheme(Scale::new(1, 10, 7, 9).unwrap());
+    /// survey.add_multiple_responses(vec![(1, 10), (2, 1)]).unwrap();
+    /// assert_eq!(survey.score(), 0);
+    /// assert!(survey.add_response(3, 0).is_err());
+    /// ```
+    pub fn with_scheme(scheme: S) -> Self {
+        Self {
+            responses: BTreeMap::new(),
+            history: BTreeMap::new(),
+            histogram: ScoreHistogram::new(),
+            nps_cache: OnceLock::new(),
+            rounding_policy: Default::default(),
+            duplicate_policy: Default::default(),
+            scheme,
+        }
+    }
 
-        let promoter_percent = 100 * promoters / total_responses;
-        let detractor_percent = 100 * detractors / total_responses;
+    /// Returns the `ClassificationScheme` used to validate and classify ratings.
+    pub fn scheme(&self) -> &S {
+        &self.scheme
+    }
 
-        self.nps_cache = Some(promoter_percent - detractor_percent);
+    /// Classifies a rating with the survey's `ClassificationScheme`.
+    pub fn classify(&self, rating: Rating) -> Classification {
+        self.scheme.classify(rating)
     }
+
     /// Adds survey responses with their quantities to the survey.
     ///
     /// This method accepts a respondent ID generator function and a slice of tuples,
@@ -279,7 +372,7 @@ impl<T: PartialEq + Ord + Clone> Survey<T> {
     /// ```
     /// # use net_promoter_score::prelude::*;
     /// # use anyhow::Result;
-    /// fn main() -> Result<(), Vec<NetPromoterScoreError>> {
+    /// fn main() -> Result<(), BatchError<i32>> {
     ///     let rating_quantities: &[(NpsRating, ScoreCount)] = &[
     ///         (1, 2),
     ///         (4, 1),
@@ -307,67 +400,34 @@ impl<T: PartialEq + Ord + Clone> Survey<T> {
     /// }
     /// ```
     ///
+    /// On success, returns the IDs generated by `respondent_id_fn` that collided with an existing
+    /// respondent, which were handled according to the survey's [`DuplicatePolicy`].
+    ///
     /// # Errors
     ///
-    /// Returns a vector of `NetPromoterScoreError` if any of the responses could not be added to the survey.
+    /// Returns a [`BatchError`] listing the position, respondent ID and reason of every response
+    /// that could not be added to the survey, along with the duplicates among the others.
     pub fn add_bulk_responses<F>(
         &mut self,
         mut respondent_id_fn: F,
         nps_scores: &[(NpsRating, ScoreCount)],
-    ) -> Result<(), Vec<NetPromoterScoreError>>
+    ) -> Result<Vec<T>, BatchError<T>>
     where
         F: FnMut() -> T,
     {
-        let errors: Vec<NetPromoterScoreError> = nps_scores
-            .iter()
-            .flat_map(|&(score, nps_scores)| {
-                let responses: Vec<(T, u8)> = (0..nps_scores)
-                    .map(|_| (respondent_id_fn(), score))
-                    .collect();
-                self.add_multiple_responses(responses)
-                    .err()
-                    .into_iter()
-                    .flatten()
-                    .collect::<Vec<_>>()
-            })
-            .collect();
-
-        if errors.is_empty() {
-            self.calculate_nps();
-            Ok(())
-        } else {
-            Err(errors)
-        }
-    }
-
-    /// Creates a new survey from a given set of responses.
-    ///
-    /// If any of the responses have an invalid rating, an error will be returned.
-    ///
-    /// # Example
-    ///
-    /// ```
-    /// # use net_promoter_score::prelude::*;
-    /// # use anyhow::Error;
-    /// let survey_result: Result<Survey<u32>, Vec<Error>> =
-    ///     Survey::<u32>::from_responses(vec![(1, 10), (2, 9), (3, 9), (4, 8), (5, 7), (6, 6)]);
-    /// ```
-    pub fn from_responses<E>(
-        responses: impl IntoIterator<Item = (T, NpsRating)>,
-    ) -> Result<Self, Vec<E>>
-    where
-        E: From<NetPromoterScoreError>,
-    {
-        let mut survey = Self::new();
-        match survey.add_multiple_responses(responses) {
-            Ok(_) => Ok(survey),
-            Err(errors) => Err(errors.into_iter().map(E::from).collect()),
-        }
+        let responses = nps_scores.iter().flat_map(|&(score, quantity)| {
+            (0..quantity)
+                .map(|_| (respondent_id_fn(), score))
+                .collect::<Vec<_>>()
+        });
+        self.ingest(responses).into_result()
     }
 
     /// Adds a response with the given respondent ID and score to the survey.
     ///
-    /// Returns a result indicating whether the addition was successful.
+    /// Returns a result indicating whether the addition was successful. If the respondent already
+    /// had a response, the collision is handled according to the survey's [`DuplicatePolicy`] and
+    /// `Ok(Some(respondent_id))` is returned; otherwise `Ok(None)` is returned.
     ///
     /// # Example
     ///
@@ -393,20 +453,50 @@ impl<T: PartialEq + Ord + Clone> Survey<T> {
     ///
     /// In the example above, a new `Survey` is created using the `new` method. Three responses are then added to the survey using the `add_response` method, with each response including a respondent ID and a score from 0 to 10. After adding the responses, the `score` method is called to calculate the Net Promoter Score (NPS) of the survey, which is then printed to the console.
     ///
-    /// If any of the responses have an invalid rating, an error will be returned.
+    /// If any of the responses have an invalid rating, an error will be returned. If the
+    /// respondent already had a response and the policy is [`DuplicatePolicy::Reject`],
+    /// `NetPromoterScoreError::DuplicateRespondent` is returned.
     pub fn add_response(
         &mut self,
         respondent_id: T,
         score: NpsRating,
-    ) -> Result<(), NetPromoterScoreError> {
+    ) -> Result<Option<T>, NetPromoterScoreError> {
+        self.scheme.rating(score)?;
         let response = SurveyResponse::new(respondent_id.clone(), score)?;
-        self.responses.insert(respondent_id, response);
-        Ok(())
+        self.insert_response(respondent_id, response)
+    }
+
+    /// Adds a response with the given respondent ID, score and timestamp to the survey.
+    ///
+    /// Timestamped responses can be queried by time with [`score_between`](Survey::score_between)
+    /// and [`time_series`](Survey::time_series).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    /// use std::time::SystemTime;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_response_at("customer 1", 9, SystemTime::now()).unwrap();
+    /// ```
+    ///
+    /// Duplicate respondents are handled as in [`add_response`](Survey::add_response). If the
+    /// response has an invalid rating, an error will be returned.
+    pub fn add_response_at(
+        &mut self,
+        respondent_id: T,
+        score: NpsRating,
+        timestamp: SystemTime,
+    ) -> Result<Option<T>, NetPromoterScoreError> {
+        self.scheme.rating(score)?;
+        let response = SurveyResponse::new_at(respondent_id.clone(), score, timestamp)?;
+        self.insert_response(respondent_id, response)
     }
 
     /// Adds multiple responses to the survey.
     ///
-    /// If any of the responses have an invalid rating, a `Vec<NetPromoterScoreError>` is returned.
+    /// If any of the responses have an invalid rating, a [`BatchError`] is returned.
     ///
     /// # Example
     ///
@@ -414,7 +504,7 @@ impl<T: PartialEq + Ord + Clone> Survey<T> {
     /// use net_promoter_score::prelude::*;
     /// use anyhow::Result;
     ///
-    /// fn main() -> Result<(), Vec<NetPromoterScoreError>> {
+    /// fn main() -> Result<(), BatchError<i32>> {
     ///     let mut survey = Survey::new();
     ///
     ///     // Adding multiple responses to the survey
@@ -430,26 +520,405 @@ impl<T: PartialEq + Ord + Clone> Survey<T> {
     ///
     /// In the example above, a new `Survey` is created using the `new` method. Multiple responses are then added to the survey using the `add_multiple_responses` method, with each response including a respondent ID and a score from 0 to 10. After adding the responses, the `score` method is called to calculate the Net Promoter Score (NPS) of the survey, which is then printed to the console.
     ///
-    /// If any of the responses have an invalid rating, a `Vec<NetPromoterScoreError>` is returned, which can be handled to identify and handle the specific errors.
+    /// If any of the responses could not be added, a [`BatchError`] is returned, which lists the position, respondent ID and reason of each one so that they can be handled. Under [`DuplicatePolicy::Reject`], this includes every respondent that already had a response.
+    ///
+    /// On success, the IDs of respondents that already had a response are returned, in input
+    /// order, so that they can be logged. How each collision was handled depends on the survey's
+    /// [`DuplicatePolicy`].
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// let duplicates = survey
+    ///     .add_multiple_responses(vec![(1, 9), (2, 8), (1, 6)])
+    ///     .unwrap();
+    /// assert_eq!(duplicates, vec![1]);
+    /// ```
     pub fn add_multiple_responses(
         &mut self,
         responses: impl IntoIterator<Item = (T, NpsRating)>,
-    ) -> Result<(), Vec<NetPromoterScoreError>> {
-        let errors: Vec<NetPromoterScoreError> = responses
-            .into_iter()
-            .filter_map(
-                |(respondent_id, score)| match self.add_response(respondent_id, score) {
-                    Ok(_) => None,
-                    Err(e) => Some(e),
-                },
-            )
-            .collect();
-        if errors.is_empty() {
-            self.calculate_nps();
-            Ok(())
-        } else {
-            Err(errors)
+    ) -> Result<Vec<T>, BatchError<T>> {
+        self.ingest(responses).into_result()
+    }
+
+    /// Adds a response with the given respondent ID, score and attributes to the survey.
+    ///
+    /// Attributes are key/value pairs such as region, plan tier or channel, which can later be
+    /// used with [`group_by`](Survey::group_by) to segment the survey.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey
+    ///     .add_response_with_attributes(1, 9, [("region", "EMEA"), ("plan", "pro")])
+    ///     .unwrap();
+    ///
+    /// let response = survey.responses().next().unwrap();
+    /// assert_eq!(response.attribute("region"), Some("EMEA"));
+    /// ```
+    ///
+    /// Duplicate respondents are handled as in [`add_response`](Survey::add_response). If the
+    /// response has an invalid rating, an error will be returned.
+    pub fn add_response_with_attributes<K, V>(
+        &mut self,
+        respondent_id: T,
+        score: NpsRating,
+        attributes: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Option<T>, NetPromoterScoreError>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.scheme.rating(score)?;
+        let response = SurveyResponse::new(respondent_id, score)?.with_attributes(attributes);
+        self.add_survey_response(response)
+    }
+
+    /// Adds a response with the given respondent ID, score and free-text comment to the survey.
+    ///
+    /// Comments can be analyzed per classification with the [`text`] module.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey
+    ///     .add_response_with_comment(1, 3, "Support never answered my ticket")
+    ///     .unwrap();
+    ///
+    /// let response = survey.get(&1).unwrap();
+    /// assert_eq!(response.comment(), Some("Support never answered my ticket"));
+    /// ```
+    ///
+    /// Duplicate respondents are handled as in [`add_response`](Survey::add_response). If the
+    /// response has an invalid rating, an error will be returned.
+    pub fn add_response_with_comment(
+        &mut self,
+        respondent_id: T,
+        score: NpsRating,
+        comment: impl Into<String>,
+    ) -> Result<Option<T>, NetPromoterScoreError> {
+        self.scheme.rating(score)?;
+        let response = SurveyResponse::new(respondent_id, score)?.with_comment(comment);
+        self.add_survey_response(response)
+    }
+
+    /// Adds an already validated `SurveyResponse` to the survey, keyed by its respondent ID.
+    ///
+    /// This is the most flexible way to add a response that carries a timestamp, attributes or
+    /// both.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    /// use std::time::SystemTime;
+    ///
+    /// let response = SurveyResponse::new_at("customer 1", 10, SystemTime::now())
+    ///     .unwrap()
+    ///     .with_attribute("channel", "email");
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_survey_response(response).unwrap();
+    /// assert_eq!(survey.score(), 100);
+    /// ```
+    ///
+    /// Duplicate respondents are handled as in [`add_response`](Survey::add_response). If the
+    /// response's rating lies outside the survey's scale, an error will be returned.
+    pub fn add_survey_response(
+        &mut self,
+        response: SurveyResponse<T>,
+    ) -> Result<Option<T>, NetPromoterScoreError> {
+        self.insert_response(response.respondent_id().clone(), response)
+    }
+
+    /// Adds multiple timestamped responses to the survey.
+    ///
+    /// Each item is a tuple of respondent ID, score and timestamp. If any of the responses have an
+    /// invalid rating, a [`BatchError`] is returned and the valid ones are still added.
+    /// On success, the IDs of respondents that already had a response are returned, as in
+    /// [`add_multiple_responses`](Survey::add_multiple_responses).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let now = SystemTime::now();
+    /// let yesterday = now - Duration::from_secs(86_400);
+    ///
+    /// let mut survey = Survey::new();
+    /// survey
+    ///     .add_multiple_responses_at(vec![(1, 9, yesterday), (2, 8, now), (3, 6, now)])
+    ///     .unwrap();
+    /// assert_eq!(survey.score_between(now..), -50);
+    /// ```
+    pub fn add_multiple_responses_at(
+        &mut self,
+        responses: impl IntoIterator<Item = (T, NpsRating, SystemTime)>,
+    ) -> Result<Vec<T>, BatchError<T>> {
+        self.ingest(responses).into_result()
+    }
+
+    /// Returns the `DuplicatePolicy` applied when a respondent who already has a response is
+    /// added again.
+    pub fn duplicate_policy(&self) -> DuplicatePolicy {
+        self.duplicate_policy
+    }
+
+    /// Sets the `DuplicatePolicy` applied when a respondent who already has a response is added
+    /// again. It only affects responses added afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.set_duplicate_policy(DuplicatePolicy::Reject);
+    ///
+    /// survey.add_response("customer 1", 9).unwrap();
+    /// assert_eq!(
+    ///     survey.add_response("customer 1", 2),
+    ///     Err(NetPromoterScoreError::DuplicateRespondent)
+    /// );
+    /// assert_eq!(survey.score(), 100);
+    /// ```
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        self.duplicate_policy = policy;
+    }
+
+    /// Returns the earlier responses of a respondent that were superseded while the survey's
+    /// policy was [`DuplicatePolicy::KeepAll`], oldest first. The current response is not
+    /// included; it is the one returned by [`responses`](Survey::responses).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.set_duplicate_policy(DuplicatePolicy::KeepAll);
+    /// survey.add_multiple_responses(vec![(1, 3), (1, 7), (1, 10)]).unwrap();
+    ///
+    /// let earlier: Vec<u8> = survey.history(&1).iter().map(|r| **r.score()).collect();
+    /// assert_eq!(earlier, vec![3, 7]);
+    /// assert_eq!(survey.score(), 100);
+    /// ```
+    pub fn history(&self, respondent_id: &T) -> &[SurveyResponse<T>] {
+        self.history
+            .get(respondent_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Removes the response of the given respondent, together with any earlier responses kept in
+    /// their [`history`](Survey::history), and returns it.
+    ///
+    /// Returns `None` if the respondent has no response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_multiple_responses(vec![(1, 10), (2, 0)]).unwrap();
+    ///
+    /// let removed = survey.remove_response(&2).unwrap();
+    /// assert_eq!(**removed.score(), 0);
+    /// assert_eq!(survey.score(), 100);
+    /// assert!(survey.remove_response(&2).is_none());
+    /// ```
+    pub fn remove_response(&mut self, respondent_id: &T) -> Option<SurveyResponse<T>> {
+        self.history.remove(respondent_id);
+        self.take_response(respondent_id)
+    }
+
+    /// Changes the score of the given respondent's response, keeping its timestamp and attributes,
+    /// and returns the previous rating.
+    ///
+    /// Under [`DuplicatePolicy::KeepAll`], the response as it was before the change is added to the
+    /// respondent's [`history`](Survey::history). Returns `Ok(None)` without changing the survey if
+    /// the respondent has no response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_response("customer 1", 2).unwrap();
+    ///
+    /// let previous = survey.update_response(&"customer 1", 9).unwrap();
+    /// assert_eq!(previous.map(|rating| *rating), Some(2));
+    /// assert_eq!(survey.score(), 100);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `score` is not a valid rating on the survey's scale.
+    pub fn update_response(
+        &mut self,
+        respondent_id: &T,
+        score: NpsRating,
+    ) -> Result<Option<Rating>, NetPromoterScoreError> {
+        let rating = self.scheme.rating(score)?;
+        let Some(response) = self.responses.get_mut(respondent_id) else {
+            return Ok(None);
+        };
+        let previous = response.score;
+        if self.duplicate_policy == DuplicatePolicy::KeepAll {
+            self.history
+                .entry(respondent_id.clone())
+                .or_default()
+                .push(response.clone());
+        }
+        response.score = rating;
+        self.histogram.replace(previous, rating);
+        self.nps_cache.take();
+        Ok(Some(previous))
+    }
+
+    /// Keeps only the responses for which `predicate` returns `true`. Respondents whose response
+    /// is removed also lose their [`history`](Survey::history).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_response_with_attributes(1, 10, [("source", "import")]).unwrap();
+    /// survey.add_response_with_attributes(2, 0, [("source", "bad-import")]).unwrap();
+    ///
+    /// survey.retain(|response| response.attribute("source") != Some("bad-import"));
+    /// assert_eq!(survey.score(), 100);
+    /// ```
+    pub fn retain(&mut self, mut predicate: impl FnMut(&SurveyResponse<T>) -> bool) {
+        let histogram = &mut self.histogram;
+        let history = &mut self.history;
+        self.responses.retain(|respondent_id, response| {
+            let keep = predicate(response);
+            if !keep {
+                histogram.remove(response.score);
+                history.remove(respondent_id);
+            }
+            keep
+        });
+        self.nps_cache.take();
+    }
+
+    /// Erases every response of the given respondents, including earlier responses kept in their
+    /// [`history`](Survey::history), e.g. to honor data-subject deletion requests.
+    ///
+    /// Returns the erased responses, so that the erasure can be audited. Unknown respondent IDs
+    /// are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.set_duplicate_policy(DuplicatePolicy::KeepAll);
+    /// survey
+    ///     .add_multiple_responses(vec![("alice", 9), ("bob", 3), ("alice", 10)])
+    ///     .unwrap();
+    ///
+    /// let erased = survey.erase_respondents(&["alice", "carol"]);
+    /// assert_eq!(erased.len(), 2);
+    /// assert!(survey.history(&"alice").is_empty());
+    /// assert_eq!(survey.score(), -100);
+    /// ```
+    pub fn erase_respondents<'a>(
+        &mut self,
+        respondent_ids: impl IntoIterator<Item = &'a T>,
+    ) -> Vec<SurveyResponse<T>>
+    where
+        T: 'a,
+    {
+        let mut erased = Vec::new();
+        for respondent_id in respondent_ids {
+            erased.extend(self.history.remove(respondent_id).unwrap_or_default());
+            erased.extend(self.take_response(respondent_id));
         }
+        erased
+    }
+
+    /// Returns the current response of the given respondent, if any.
+    pub fn get(&self, respondent_id: &T) -> Option<&SurveyResponse<T>> {
+        self.responses.get(respondent_id)
+    }
+
+    /// Moves the current responses of `other` into this survey, applying this survey's
+    /// [`DuplicatePolicy`] to respondents present in both.
+    ///
+    /// On success, returns the IDs of respondents that collided with an existing response, as the
+    /// add methods do. The history kept by `other` under [`DuplicatePolicy::KeepAll`] is not
+    /// merged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_multiple_responses(vec![(1, 10), (2, 0)]).unwrap();
+    /// let mut shard = Survey::new();
+    /// shard.add_multiple_responses(vec![(2, 9), (3, 9)]).unwrap();
+    ///
+    /// assert_eq!(survey.merge(shard).unwrap(), vec![2]);
+    /// assert_eq!(survey.len(), 3);
+    /// assert_eq!(survey.score(), 100);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BatchError`] listing the responses that could not be added, such as
+    /// `NetPromoterScoreError::DuplicateRespondent` under [`DuplicatePolicy::Reject`] or ratings
+    /// outside this survey's scale, with their respondent IDs. The other responses are still
+    /// merged.
+    pub fn merge<S2>(&mut self, other: Survey<T, S2>) -> Result<Vec<T>, BatchError<T>> {
+        self.ingest(other.into_iter().map(|(_, response)| response))
+            .into_result()
+    }
+
+    /// Summarizes the survey as an [`NpsAggregate`] of per-rating counts, keeping per-attribute
+    /// counts for each of `keys` and the time bounds of the timestamped responses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_response_with_attributes(1, 10, [("plan", "pro")]).unwrap();
+    /// survey.add_response(2, 6).unwrap();
+    ///
+    /// let aggregate = survey.aggregate(&["plan"]);
+    /// assert_eq!(aggregate.histogram(), survey.histogram());
+    /// assert_eq!(aggregate.group("plan").unwrap()["pro"].count(10), 1);
+    /// ```
+    pub fn aggregate(&self, keys: &[&str]) -> NpsAggregate {
+        NpsAggregate::from_responses(self.responses(), keys)
+    }
+
+    /// Returns the number of respondents with a response.
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Returns `true` if the survey has no responses.
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
     }
 
     /// Returns a slice of the survey responses.
@@ -466,7 +935,7 @@ impl<T: PartialEq + Ord + Clone> Survey<T> {
     /// # Arguments
     ///
     /// * `classification` - A `Classification` enumeration value representing the desired segment
-    /// (either `Detractor`, `Passive`, or `Promoter`) to filter the survey responses.
+    ///   (either `Detractor`, `Passive`, or `Promoter`) to filter the survey responses.
     ///
     /// # Example
     ///
@@ -489,11 +958,10 @@ impl<T: PartialEq + Ord + Clone> Survey<T> {
     ///     ("r11", 1),
     ///     ("r12", 1),
     /// ];
-
+    ///
     /// for (respondent_id, score) in responses {
     ///     survey.add_response(respondent_id, score).unwrap();
     /// }
-
     ///
     /// let detractors: Vec<&SurveyResponse<_>> = survey.segment(Classification::Detractor);
     /// let passives: Vec<&SurveyResponse<_>> = survey.segment(Classification::Passive);
@@ -510,45 +978,492 @@ impl<T: PartialEq + Ord + Clone> Survey<T> {
     /// the number of responses in each classification.
     pub fn segment(&self, classification: Classification) -> Vec<&SurveyResponse<T>> {
         self.responses()
-            .filter(|response| Classification::from(response.score()) == classification)
+            .filter(|response| self.classify(response.score) == classification)
             .collect()
     }
+    /// Groups the survey responses by the value of the attribute `key` and returns the segment
+    /// counts of each group.
+    ///
+    /// Each [`SegmentCounts`] provides the group's NPS through
+    /// [`score_exact`](SegmentCounts::score_exact) and its confidence interval through
+    /// [`confidence_interval`](SegmentCounts::confidence_interval). Responses without the attribute
+    /// are left out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_response_with_attributes(1, 10, [("region", "EMEA")]).unwrap();
+    /// survey.add_response_with_attributes(2, 3, [("region", "EMEA")]).unwrap();
+    /// survey.add_response_with_attributes(3, 9, [("region", "APAC")]).unwrap();
+    /// survey.add_response(4, 0).unwrap();
+    ///
+    /// let regions = survey.group_by("region");
+    /// assert_eq!(regions.len(), 2);
+    /// assert_eq!(regions["EMEA"].score_exact(), 0.0);
+    /// assert_eq!(regions["APAC"].score_exact(), 100.0);
+    /// ```
+    pub fn group_by(&self, key: &str) -> BTreeMap<String, SegmentCounts> {
+        let mut groups: BTreeMap<String, SegmentCounts> = BTreeMap::new();
+        for response in self.responses() {
+            if let Some(value) = response.attribute(key) {
+                groups
+                    .entry(value.to_owned())
+                    .or_default()
+                    .record(self.classify(response.score));
+            }
+        }
+        groups
+    }
+
+    /// Groups the survey responses by the values of several attributes at once, e.g. region ×
+    /// plan tier, and returns the segment counts of each combination.
+    ///
+    /// The map keys hold one attribute value per entry in `keys`, in the same order. Responses
+    /// missing any of the attributes are left out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey
+    ///     .add_response_with_attributes(1, 10, [("region", "EMEA"), ("plan", "pro")])
+    ///     .unwrap();
+    /// survey
+    ///     .add_response_with_attributes(2, 2, [("region", "EMEA"), ("plan", "free")])
+    ///     .unwrap();
+    ///
+    /// let groups = survey.group_by_keys(&["region", "plan"]);
+    /// let emea_pro = &groups[&vec!["EMEA".to_string(), "pro".to_string()]];
+    /// assert_eq!(emea_pro.promoters, 1);
+    /// ```
+    pub fn group_by_keys(&self, keys: &[&str]) -> BTreeMap<Vec<String>, SegmentCounts> {
+        let mut groups: BTreeMap<Vec<String>, SegmentCounts> = BTreeMap::new();
+        for response in self.responses() {
+            let values: Option<Vec<String>> = keys
+                .iter()
+                .map(|key| response.attribute(key).map(str::to_owned))
+                .collect();
+            if let Some(values) = values {
+                groups
+                    .entry(values)
+                    .or_default()
+                    .record(self.classify(response.score));
+            }
+        }
+        groups
+    }
+
+    /// Cross-tabulates the survey responses by two attributes, returning the segment counts for
+    /// each `row_key` value and, within it, each `column_key` value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey
+    ///     .add_response_with_attributes(1, 10, [("region", "EMEA"), ("plan", "pro")])
+    ///     .unwrap();
+    /// survey
+    ///     .add_response_with_attributes(2, 2, [("region", "APAC"), ("plan", "pro")])
+    ///     .unwrap();
+    ///
+    /// let pivot = survey.pivot("region", "plan");
+    /// assert_eq!(pivot["EMEA"]["pro"].score_exact(), 100.0);
+    /// assert_eq!(pivot["APAC"]["pro"].score_exact(), -100.0);
+    /// ```
+    pub fn pivot(
+        &self,
+        row_key: &str,
+        column_key: &str,
+    ) -> BTreeMap<String, BTreeMap<String, SegmentCounts>> {
+        let mut pivot: BTreeMap<String, BTreeMap<String, SegmentCounts>> = BTreeMap::new();
+        for (mut values, counts) in self.group_by_keys(&[row_key, column_key]) {
+            let column = values.pop().unwrap_or_default();
+            let row = values.pop().unwrap_or_default();
+            pivot.entry(row).or_default().insert(column, counts);
+        }
+        pivot
+    }
+
+    /// Returns the number of detractors, passives and promoters in the survey.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_multiple_responses(vec![(1, 9), (2, 8), (3, 6), (4, 3)]).unwrap();
+    ///
+    /// let counts = survey.segment_counts();
+    /// assert_eq!(counts, SegmentCounts { detractors: 2, passives: 1, promoters: 1 });
+    /// assert_eq!(counts.total(), 4);
+    /// ```
+    pub fn segment_counts(&self) -> SegmentCounts {
+        self.histogram.segment_counts_with(&self.scheme)
+    }
+
+    /// Returns the per-rating counts of the survey responses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_multiple_responses(vec![(1, 9), (2, 9), (3, 6)]).unwrap();
+    ///
+    /// assert_eq!(survey.histogram().count(9), 2);
+    /// assert_eq!(survey.histogram().count(6), 1);
+    /// ```
+    pub fn histogram(&self) -> &ScoreHistogram {
+        &self.histogram
+    }
+
+    /// Evaluates a [`Metric`], such as CSAT or the mean rating, against the survey responses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_multiple_responses(vec![(1, 10), (2, 9), (3, 6), (4, 3)]).unwrap();
+    ///
+    /// assert_eq!(survey.evaluate(&Nps).unwrap(), 0.0);
+    /// assert_eq!(survey.evaluate(&TopBox(2)).unwrap(), 50.0);
+    /// assert_eq!(survey.evaluate(&Median).unwrap(), 7.5);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error raised by the metric, such as
+    /// `NetPromoterScoreError::InsufficientResponses` for an empty survey.
+    pub fn evaluate<M: Metric + ?Sized>(&self, metric: &M) -> Result<f64, NetPromoterScoreError> {
+        metric.evaluate(&self.histogram, &self.scheme)
+    }
+
+    /// Groups the survey responses by the value of the attribute `key` and evaluates a [`Metric`]
+    /// for each group. Responses without the attribute are left out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_response_with_attributes(1, 10, [("region", "EMEA")]).unwrap();
+    /// survey.add_response_with_attributes(2, 4, [("region", "EMEA")]).unwrap();
+    /// survey.add_response_with_attributes(3, 8, [("region", "APAC")]).unwrap();
+    ///
+    /// let means = survey.evaluate_by("region", &Mean);
+    /// assert_eq!(means["EMEA"], Ok(7.0));
+    /// assert_eq!(means["APAC"], Ok(8.0));
+    /// ```
+    pub fn evaluate_by<M: Metric + ?Sized>(
+        &self,
+        key: &str,
+        metric: &M,
+    ) -> BTreeMap<String, Result<f64, NetPromoterScoreError>> {
+        self.histograms_by(key)
+            .into_iter()
+            .map(|(value, histogram)| (value, metric.evaluate(&histogram, &self.scheme)))
+            .collect()
+    }
+
+    /// Groups the survey responses by the value of the attribute `key` and returns the per-rating
+    /// counts of each group. Responses without the attribute are left out.
+    pub fn histograms_by(&self, key: &str) -> BTreeMap<String, ScoreHistogram> {
+        let mut groups: BTreeMap<String, ScoreHistogram> = BTreeMap::new();
+        for response in self.responses() {
+            if let Some(value) = response.attribute(key) {
+                groups
+                    .entry(value.to_owned())
+                    .or_default()
+                    .insert(response.score);
+            }
+        }
+        groups
+    }
+
+    /// Returns the number of detractors, passives and promoters among the responses whose
+    /// timestamp falls within `range`. Responses without a timestamp are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_response_at(1, 10, UNIX_EPOCH + Duration::from_secs(10)).unwrap();
+    /// survey.add_response_at(2, 0, UNIX_EPOCH + Duration::from_secs(20)).unwrap();
+    /// survey.add_response(3, 8).unwrap();
+    ///
+    /// let counts = survey.segment_counts_between(..UNIX_EPOCH + Duration::from_secs(20));
+    /// assert_eq!(counts, SegmentCounts { detractors: 0, passives: 0, promoters: 1 });
+    /// ```
+    pub fn segment_counts_between(&self, range: impl RangeBounds<SystemTime>) -> SegmentCounts {
+        self.responses()
+            .filter(|response| {
+                response
+                    .timestamp()
+                    .is_some_and(|timestamp| range.contains(&timestamp))
+            })
+            .fold(SegmentCounts::default(), |mut counts, response| {
+                counts.record(self.classify(response.score));
+                counts
+            })
+    }
+
+    /// Returns the exact Net Promoter Score (NPS) of the responses whose timestamp falls within
+    /// `range`, or `0.0` if there are none. Responses without a timestamp are ignored.
+    pub fn score_exact_between(&self, range: impl RangeBounds<SystemTime>) -> f64 {
+        self.segment_counts_between(range).score_exact()
+    }
+
+    /// Returns the Net Promoter Score (NPS) of the responses whose timestamp falls within `range`,
+    /// rounded with the survey's [`RoundingPolicy`]. Responses without a timestamp are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// let now = SystemTime::now();
+    /// let last_week = now - Duration::from_secs(7 * 86_400);
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_response_at(1, 3, now - Duration::from_secs(30 * 86_400)).unwrap();
+    /// survey.add_response_at(2, 10, now - Duration::from_secs(86_400)).unwrap();
+    ///
+    /// assert_eq!(survey.score_between(last_week..=now), 100);
+    /// ```
+    pub fn score_between(&self, range: impl RangeBounds<SystemTime>) -> i32 {
+        self.rounding_policy.round(self.score_exact_between(range)) as i32
+    }
+
+    /// Returns an iterator over the survey's timestamped responses bucketed by calendar `period`.
+    ///
+    /// Each [`TimeBucket`](timeseries::TimeBucket) holds the NPS and segment counts of its
+    /// responses. Call [`rolling`](TimeSeries::rolling) on the result for rolling windows. See the
+    /// [`timeseries`] module for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    /// use std::time::SystemTime;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_response_at(1, 9, SystemTime::now()).unwrap();
+    ///
+    /// for bucket in survey.time_series(Period::Week) {
+    ///     println!("{:?}: {:?} ({} responses)", bucket.start, bucket.nps, bucket.counts.total());
+    /// }
+    /// ```
+    pub fn time_series(&self, period: Period) -> TimeSeries {
+        TimeSeries::new(period, self.responses(), |rating| self.classify(rating))
+    }
+
+    /// Returns a confidence interval for the Net Promoter Score (NPS) of the survey, using the
+    /// standard trinomial variance method.
+    ///
+    /// `level` is the confidence level as a fraction strictly between 0 and 1, such as `0.95`. The
+    /// returned [`ConfidenceInterval`] holds the lower and upper bounds, the standard error and the
+    /// margin of error, all in NPS points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_bulk_responses_auto_id(&[(3, 30), (7, 30), (10, 40)]).unwrap();
+    ///
+    /// let interval = survey.confidence_interval(0.95).unwrap();
+    /// println!("NPS between {:.1} and {:.1}", interval.lower, interval.upper);
+    /// # assert!((interval.standard_error - 8.3485).abs() < 1e-4);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetPromoterScoreError::InvalidConfidenceLevel` if `level` is not between 0 and 1,
+    /// and `NetPromoterScoreError::InsufficientResponses` if the survey has fewer than two responses.
+    pub fn confidence_interval(
+        &self,
+        level: f64,
+    ) -> Result<ConfidenceInterval, NetPromoterScoreError> {
+        self.confidence_interval_with(level, IntervalMethod::default())
+    }
+
+    /// Returns a confidence interval for the Net Promoter Score (NPS) of the survey, using the
+    /// given `IntervalMethod`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey.add_response(1, 10).unwrap();
+    ///
+    /// // A single response is not enough for the trinomial method, but the adjusted Wald method
+    /// // still yields a usable interval.
+    /// assert!(survey.confidence_interval(0.9).is_err());
+    /// let interval = survey
+    ///     .confidence_interval_with(0.9, IntervalMethod::AdjustedWald)
+    ///     .unwrap();
+    /// assert!(interval.lower < 0.0);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`SegmentCounts::confidence_interval`].
+    pub fn confidence_interval_with(
+        &self,
+        level: f64,
+        method: IntervalMethod,
+    ) -> Result<ConfidenceInterval, NetPromoterScoreError> {
+        self.segment_counts().confidence_interval(level, method)
+    }
+
     /// Returns the Net Promoter Score (NPS) of the survey.
     ///
     /// The NPS is a metric used to gauge the loyalty of customers or clients. It is calculated
-    /// as the percentage of Promoters minus the percentage of Detractors. Promoters are respondents
-    /// who gave a rating of 9 or 10, Detractors are respondents who gave a rating of 0 to 6, and
-    /// Passives are respondents who gave a rating of 7 or 8.
+    /// as the percentage of Promoters minus the percentage of Detractors, where each response is
+    /// classified by the survey's [`ClassificationScheme`]. Under the default [`NpsScheme`],
+    /// Promoters gave a rating of 9 or 10, Passives 7 or 8 and Detractors 0 to 6.
+    ///
+    /// A higher NPS indicates a greater proportion of satisfied customers who are likely to recommend
+    /// the product, service, or experience, whereas a lower NPS indicates dissatisfaction and a higher
+    /// risk of customers switching to competitors. The NPS can range from -100 (all Detractors) to
+    /// 100 (all Promoters).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// // Create a new survey and add responses
+    /// let mut survey: Survey<u8> = Survey::new();
+    /// // Add survey responses to the survey here...
+    ///
+    /// // Calculate the Net Promoter Score
+    /// let nps = survey.score();
+    /// println!("The calculated NPS is: {}", nps);
+    /// ```
+    ///
+    /// In the above example, a new `Survey` is created, and survey responses are added to it.
+    /// The `score` method is then called to calculate the Net Promoter Score (NPS) based on the
+    /// given responses. The calculated NPS, which can range from -100 to 100, is then printed to the console.
+    ///
+    /// The score is derived from [`score_exact`](Survey::score_exact) and rounded once, using the
+    /// survey's [`RoundingPolicy`] (half-up by default).
+    pub fn score(&self) -> i32 {
+        let policy = self.rounding_policy;
+        self.score_with(policy)
+    }
+
+    /// Returns the Net Promoter Score (NPS) of the survey rounded with the given `RoundingPolicy`,
+    /// regardless of the policy configured on the survey.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// // One promoter and seven passives: the exact NPS is 12.5
+    /// let mut survey = Survey::new();
+    /// survey.add_response(1, 10).unwrap();
+    /// for respondent_id in 2..=8 {
+    ///     survey.add_response(respondent_id, 8).unwrap();
+    /// }
+    ///
+    /// assert_eq!(survey.score_with(RoundingPolicy::Truncate), 12);
+    /// assert_eq!(survey.score_with(RoundingPolicy::HalfUp), 13);
+    /// assert_eq!(survey.score_with(RoundingPolicy::HalfEven), 12);
+    /// ```
+    pub fn score_with(&self, policy: RoundingPolicy) -> i32 {
+        policy.round(self.score_exact()) as i32
+    }
+
+    /// Returns the exact, unrounded Net Promoter Score (NPS) of the survey.
+    ///
+    /// Unlike [`score`](Survey::score), the promoter and detractor percentages are not truncated
+    /// before being subtracted, so the result is the precise value in the range -100.0 to 100.0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let mut survey = Survey::new();
+    /// survey
+    ///     .add_multiple_responses(vec![(1, 10), (2, 9), (3, 9), (4, 8), (5, 7), (6, 6)])
+    ///     .unwrap();
+    ///
+    /// let nps = survey.score_exact();
+    /// println!("The exact NPS is: {:.1}", nps);
+    /// # assert!((nps - 100.0 / 3.0).abs() < f64::EPSILON);
+    /// ```
     ///
-    /// A higher NPS indicates a greater proportion of satisfied customers who are likely to recommend
-    /// the product, service, or experience, whereas a lower NPS indicates dissatisfaction and a higher
-    /// risk of customers switching to competitors. The NPS can range from -100 (all Detractors) to
-    /// 100 (all Promoters).
+    /// The score is cached until the survey is next modified. Scoring only needs a shared
+    /// reference, so a survey can be scored from behind an `Arc` or inside an iterator chain.
+    pub fn score_exact(&self) -> f64 {
+        *self
+            .nps_cache
+            .get_or_init(|| self.segment_counts().score_exact())
+    }
+
+    /// Returns the response counts, segment percentages, score and rating distribution of the
+    /// survey together.
     ///
     /// # Example
     ///
     /// ```
     /// use net_promoter_score::prelude::*;
+    /// use std::sync::Arc;
     ///
-    /// // Create a new survey and add responses
-    /// let mut survey: Survey<u8> = Survey::new();
-    /// // Add survey responses to the survey here...
+    /// let mut survey = Survey::new();
+    /// survey.add_multiple_responses(vec![(1, 10), (2, 9), (3, 7), (4, 2)]).unwrap();
+    ///
+    /// let shared = Arc::new(survey);
+    /// let summary = shared.summary();
+    /// assert_eq!(summary.total, 4);
+    /// assert_eq!(summary.promoter_percentage, 50.0);
+    /// assert_eq!(summary.score, 25);
+    /// ```
+    pub fn summary(&self) -> NpsSummary {
+        NpsSummary::from_histogram(&self.histogram, &self.scheme, self.rounding_policy)
+    }
+
+    /// Returns the `RoundingPolicy` used by [`score`](Survey::score).
+    pub fn rounding_policy(&self) -> RoundingPolicy {
+        self.rounding_policy
+    }
+
+    /// Sets the `RoundingPolicy` used by [`score`](Survey::score).
+    ///
+    /// # Example
     ///
-    /// // Calculate the Net Promoter Score
-    /// let nps = survey.score();
-    /// println!("The calculated NPS is: {}", nps);
     /// ```
+    /// use net_promoter_score::prelude::*;
     ///
-    /// In the above example, a new `Survey` is created, and survey responses are added to it.
-    /// The `score` method is then called to calculate the Net Promoter Score (NPS) based on the
-    /// given responses. The calculated NPS, which can range from -100 to 100, is then printed to the console.
-    pub fn score(&mut self) -> i32 {
-        if let Some(cached_nps) = self.nps_cache {
-            cached_nps
-        } else {
-            self.calculate_nps();
-            self.nps_cache.unwrap_or(0)
-        }
+    /// let mut survey: Survey<u32> = Survey::new();
+    /// survey.set_rounding_policy(RoundingPolicy::BANKERS);
+    /// assert_eq!(survey.rounding_policy(), RoundingPolicy::HalfEven);
+    /// ```
+    pub fn set_rounding_policy(&mut self, policy: RoundingPolicy) {
+        self.rounding_policy = policy;
     }
 }
 /// A specialized implementation of the [`Survey`] struct for respondent IDs of type i32.
@@ -562,7 +1477,7 @@ impl<T: PartialEq + Ord + Clone> Survey<T> {
 /// use net_promoter_score::prelude::*;
 /// use anyhow::Result;
 ///
-/// fn main() -> Result<(), Vec<NetPromoterScoreError>> {
+/// fn main() -> Result<(), BatchError<i32>> {
 ///     let rating_quantities: &[(NpsRating, ScoreCount)] = &[
 ///         (1, 2),
 ///         (4, 1),
@@ -580,7 +1495,7 @@ impl<T: PartialEq + Ord + Clone> Survey<T> {
 ///     Ok(())
 /// }
 /// ```
-impl Survey<i32> {
+impl<S: ClassificationScheme> Survey<i32, S> {
     /// Adds survey responses with their quantities to the survey with auto-generated unique
     /// respondent IDs of type `i32`, starting at 1.
     ///
@@ -593,7 +1508,7 @@ impl Survey<i32> {
     /// ```
     /// # use net_promoter_score::prelude::*;
     /// # use anyhow::Result;
-    /// fn main() -> Result<(), Vec<NetPromoterScoreError>> {
+    /// fn main() -> Result<(), BatchError<i32>> {
     ///     let rating_quantities: &[(NpsRating, ScoreCount)] = &[
     ///         (1, 2),
     ///         (4, 1),
@@ -613,13 +1528,17 @@ impl Survey<i32> {
     /// }
     /// ```
     ///
+    /// IDs always start at 1, so calling this method again on the same survey collides with the
+    /// earlier responses; the colliding IDs are returned on success.
+    ///
     /// # Errors
     ///
-    /// Returns a vector of `NetPromoterScoreError` if any of the responses could not be added to the survey.
+    /// Returns a [`BatchError`] listing the position, respondent ID and reason of every response
+    /// that could not be added to the survey, along with the duplicates among the others.
     pub fn add_bulk_responses_auto_id(
         &mut self,
         nps_scores: &[(NpsRating, ScoreCount)],
-    ) -> Result<(), Vec<NetPromoterScoreError>> {
+    ) -> Result<Vec<i32>, BatchError<i32>> {
         let mut respondent_id = 1i32;
         let respondent_id_fn = || {
             let current_id = respondent_id;
@@ -630,18 +1549,83 @@ impl Survey<i32> {
         self.add_bulk_responses(respondent_id_fn, nps_scores)
     }
 }
+// Every mutation of `responses` must keep the rating histogram in step with the stored responses
+// and invalidate the cached score. Insertions and removals go through these methods.
+impl<T: Ord + Clone, S: ClassificationScheme> Survey<T, S> {
+    // Rejects ratings outside the survey's scale and, under `DuplicatePolicy::Reject`, responses of
+    // respondents that already have one. `duplicate` tells whether the respondent does.
+    pub(crate) fn validate_response(
+        &self,
+        response: &SurveyResponse<T>,
+        duplicate: bool,
+    ) -> Result<(), NetPromoterScoreError> {
+        self.scheme.rating(*response.score)?;
+        if duplicate && self.duplicate_policy == DuplicatePolicy::Reject {
+            return Err(NetPromoterScoreError::DuplicateRespondent);
+        }
+        Ok(())
+    }
+
+    // Returns the respondent ID if it collided with an existing response. Responses that fail
+    // `validate_response` are not added.
+    fn insert_response(
+        &mut self,
+        respondent_id: T,
+        response: SurveyResponse<T>,
+    ) -> Result<Option<T>, NetPromoterScoreError> {
+        self.validate_response(&response, self.responses.contains_key(&respondent_id))?;
+        let mut existing = match self.responses.entry(respondent_id) {
+            Entry::Vacant(entry) => {
+                self.histogram.insert(response.score);
+                entry.insert(response);
+                self.nps_cache.take();
+                return Ok(None);
+            }
+            Entry::Occupied(entry) => entry,
+        };
+        match self.duplicate_policy {
+            // Rejected duplicates never get here.
+            DuplicatePolicy::KeepFirst | DuplicatePolicy::Reject => {}
+            DuplicatePolicy::KeepLast | DuplicatePolicy::KeepAll => {
+                self.histogram.replace(existing.get().score, response.score);
+                let replaced = existing.insert(response);
+                if self.duplicate_policy == DuplicatePolicy::KeepAll {
+                    self.history
+                        .entry(existing.key().clone())
+                        .or_default()
+                        .push(replaced);
+                }
+                self.nps_cache.take();
+            }
+        }
+        Ok(Some(existing.key().clone()))
+    }
+
+    fn take_response(&mut self, respondent_id: &T) -> Option<SurveyResponse<T>> {
+        let removed = self.responses.remove(respondent_id)?;
+        self.histogram.remove(removed.score);
+        self.nps_cache.take();
+        Some(removed)
+    }
+}
+
 // Default trait implementation to create a new empty survey
-impl<T> Default for Survey<T> {
+impl<T, S: Default> Default for Survey<T, S> {
     fn default() -> Self {
         Self {
             responses: BTreeMap::new(),
+            history: BTreeMap::new(),
+            histogram: ScoreHistogram::new(),
             nps_cache: Default::default(),
+            rounding_policy: Default::default(),
+            duplicate_policy: Default::default(),
+            scheme: Default::default(),
         }
     }
 }
 
 // Implementing IntoIterator for the Survey type, to allow iterating through survey responses.
-impl<'a, T> IntoIterator for &'a Survey<T> {
+impl<'a, T, S> IntoIterator for &'a Survey<T, S> {
     type Item = &'a SurveyResponse<T>;
     type IntoIter = std::collections::btree_map::Values<'a, T, SurveyResponse<T>>;
 
@@ -650,7 +1634,7 @@ impl<'a, T> IntoIterator for &'a Survey<T> {
     }
 }
 
-impl<T> IntoIterator for Survey<T> {
+impl<T, S> IntoIterator for Survey<T, S> {
     type Item = (T, SurveyResponse<T>);
     type IntoIter = std::collections::btree_map::IntoIter<T, SurveyResponse<T>>;
 
@@ -660,35 +1644,258 @@ impl<T> IntoIterator for Survey<T> {
 }
 // Implementing the FromIterator trait for the Survey type.
 // This allows constructing a Survey from a list of valid SurveyResponse items.
-impl<T: Ord + Clone> FromIterator<Result<(T, SurveyResponse<T>), NetPromoterScoreError>>
-    for Survey<T>
+impl<T, S> FromIterator<Result<(T, SurveyResponse<T>), NetPromoterScoreError>> for Survey<T, S>
+where
+    T: Ord + Clone,
+    S: ClassificationScheme + Default,
 {
     fn from_iter<I: IntoIterator<Item = Result<(T, SurveyResponse<T>), NetPromoterScoreError>>>(
         iter: I,
     ) -> Self {
         let iterator = iter.into_iter();
-        let mut survey = Survey {
-            responses: BTreeMap::new(),
-            nps_cache: Default::default(),
-        };
+        let mut survey = Survey::default();
         survey.extend(iterator.filter_map(Result::ok));
         survey
     }
 }
 // Implementing the Extend trait for the Survey type.
-// This allows extending a survey with additional valid SurveyResponses.
+// This allows extending a survey with additional valid SurveyResponses. Duplicate respondents are
+// handled according to the survey's DuplicatePolicy; under DuplicatePolicy::Reject they are skipped,
+// as are responses whose rating lies outside the survey's scale.
 //---------------------------------------------------------------------------
-impl<T: Clone + Ord> Extend<(T, SurveyResponse<T>)> for Survey<T> {
+impl<T: Clone + Ord, S: ClassificationScheme> Extend<(T, SurveyResponse<T>)> for Survey<T, S> {
     fn extend<I: IntoIterator<Item = (T, SurveyResponse<T>)>>(&mut self, iter: I) {
-        self.responses.extend(iter);
+        for (respondent_id, response) in iter {
+            // A rejected response leaves the survey unchanged, which is all Extend can do with it.
+            let _ = self.insert_response(respondent_id, response);
+        }
+    }
+}
+
+/// The number of detractors, passives and promoters in a set of survey responses, as classified
+/// by the survey's [`ClassificationScheme`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SegmentCounts {
+    /// The number of responses classified as [`Classification::Detractor`].
+    pub detractors: ScoreCount,
+    /// The number of responses classified as [`Classification::Passive`].
+    pub passives: ScoreCount,
+    /// The number of responses classified as [`Classification::Promoter`].
+    pub promoters: ScoreCount,
+}
+
+impl SegmentCounts {
+    /// Returns the total number of responses.
+    pub fn total(&self) -> ScoreCount {
+        self.detractors + self.passives + self.promoters
+    }
+
+    /// Counts one more response with the given classification.
+    pub fn record(&mut self, classification: Classification) {
+        match classification {
+            Classification::Detractor => self.detractors += 1,
+            Classification::Passive => self.passives += 1,
+            Classification::Promoter => self.promoters += 1,
+        }
+    }
+
+    /// Returns the exact Net Promoter Score of these counts, or `0.0` if there are no responses.
+    pub fn score_exact(&self) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        100.0 * (self.promoters as f64 - self.detractors as f64) / total as f64
+    }
+}
+
+impl Add for SegmentCounts {
+    type Output = SegmentCounts;
+
+    fn add(mut self, other: SegmentCounts) -> SegmentCounts {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign for SegmentCounts {
+    fn add_assign(&mut self, other: SegmentCounts) {
+        self.detractors += other.detractors;
+        self.passives += other.passives;
+        self.promoters += other.promoters;
+    }
+}
+
+/// How a `Survey` handles a response from a respondent who already has one.
+///
+/// # Example
+///
+/// ```
+/// use net_promoter_score::prelude::*;
+///
+/// let mut survey = Survey::new();
+/// survey.set_duplicate_policy(DuplicatePolicy::KeepFirst);
+/// let duplicates = survey.add_multiple_responses(vec![(1, 10), (1, 0)]).unwrap();
+///
+/// assert_eq!(duplicates, vec![1]);
+/// assert_eq!(survey.score(), 100);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DuplicatePolicy {
+    /// Keeps the response that was added first and discards later ones.
+    KeepFirst,
+    /// Replaces the earlier response with the one added last.
+    #[default]
+    KeepLast,
+    /// Refuses later responses with `NetPromoterScoreError::DuplicateRespondent`.
+    Reject,
+    /// Scores the response added last and keeps the earlier ones in the respondent's
+    /// [`history`](Survey::history).
+    KeepAll,
+}
+
+/// A response that could not be added to a survey, as listed in a [`BatchError`] or an
+/// [`IngestReport`](crate::ingest::IngestReport).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RejectedEntry<T> {
+    /// The 0-based position of the response in the batch.
+    pub index: usize,
+    /// The respondent ID of the response.
+    pub respondent_id: T,
+    /// Why the response was rejected.
+    pub reason: NetPromoterScoreError,
+}
+
+impl<T: Display> Display for RejectedEntry<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "entry {} (respondent {}): {}",
+            self.index, self.respondent_id, self.reason
+        )
+    }
+}
+
+/// The error returned by the batch methods of a `Survey`, such as
+/// [`add_multiple_responses`](Survey::add_multiple_responses), when some responses could not be
+/// added. The other responses are still added.
+///
+/// It holds the rejected entries and duplicates of the batch's
+/// [`IngestReport`](crate::ingest::IngestReport), as returned by
+/// [`IngestReport::into_result`](crate::ingest::IngestReport::into_result).
+///
+/// # Example
+///
+/// ```
+/// use net_promoter_score::prelude::*;
+///
+/// let mut survey = Survey::new();
+/// survey.set_duplicate_policy(DuplicatePolicy::Reject);
+/// let error = survey
+///     .add_multiple_responses(vec![("ana", 10), ("ben", 12), ("ana", 3)])
+///     .unwrap_err();
+///
+/// assert_eq!(error.rejected[0].respondent_id, "ben");
+/// assert_eq!(error.rejected[1].index, 2);
+/// assert_eq!(error.rejected[1].reason, NetPromoterScoreError::DuplicateRespondent);
+/// assert_eq!(survey.responses().count(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchError<T> {
+    /// The responses that were not added, in input order. Under [`DuplicatePolicy::Reject`], this
+    /// includes every respondent that collided with an existing response.
+    pub rejected: Vec<RejectedEntry<T>>,
+    /// The IDs of added respondents that collided with an existing response, in input order, as
+    /// returned on success.
+    pub duplicates: Vec<T>,
+}
+
+impl<T> BatchError<T> {
+    /// Returns the reasons the rejected responses were not added, in input order.
+    pub fn errors(&self) -> impl Iterator<Item = &NetPromoterScoreError> {
+        self.rejected.iter().map(|entry| &entry.reason)
+    }
+}
+
+impl<T: Display> Display for BatchError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} entries could not be added", self.rejected.len())?;
+        for (position, entry) in self.rejected.iter().enumerate() {
+            let separator = if position == 0 { ": " } else { "; " };
+            write!(f, "{}{}", separator, entry)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Debug + Display> std::error::Error for BatchError<T> {}
+
+/// The policy used to round the exact, fractional Net Promoter Score to a whole number.
+///
+/// Rounding is applied once, to the final score, so the promoter and detractor percentages are
+/// never rounded individually.
+///
+/// # Example
+///
+/// ```
+/// use net_promoter_score::prelude::*;
+///
+/// assert_eq!(RoundingPolicy::Truncate.round(-12.5), -12.0);
+/// assert_eq!(RoundingPolicy::HalfUp.round(-12.5), -13.0);
+/// assert_eq!(RoundingPolicy::HalfEven.round(-12.5), -12.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RoundingPolicy {
+    /// Discards the fractional part, rounding toward zero.
+    Truncate,
+    /// Rounds to the nearest whole number, with halfway values rounded away from zero.
+    #[default]
+    HalfUp,
+    /// Rounds to the nearest whole number, with halfway values rounded to the nearest even number.
+    HalfEven,
+}
+
+impl RoundingPolicy {
+    /// Banker's rounding, which is another name for [`RoundingPolicy::HalfEven`].
+    pub const BANKERS: RoundingPolicy = RoundingPolicy::HalfEven;
+
+    /// Rounds `value` to a whole number according to this policy.
+    pub fn round(self, value: f64) -> f64 {
+        match self {
+            RoundingPolicy::Truncate => value.trunc(),
+            RoundingPolicy::HalfUp => value.round(),
+            RoundingPolicy::HalfEven => value.round_ties_even(),
+        }
     }
 }
 
-/// A single survey response, including the respondent ID of type `T` and the score of type `Rating`.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// A single survey response, including the respondent ID of type `T`, the score of type `Rating`,
+/// an optional timestamp and any number of respondent attributes.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SurveyResponse<T> {
     respondent_id: T,
     score: Rating,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    timestamp: Option<SystemTime>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "BTreeMap::is_empty")
+    )]
+    attributes: BTreeMap<String, String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    comment: Option<String>,
 }
 
 impl<T: PartialEq> SurveyResponse<T> {
@@ -698,9 +1905,71 @@ impl<T: PartialEq> SurveyResponse<T> {
         Ok(Self {
             respondent_id,
             score: nps_rating,
+            timestamp: None,
+            attributes: BTreeMap::new(),
+            comment: None,
         })
     }
 
+    /// Creates a new survey response with the given respondent ID, score and timestamp.
+    pub fn new_at(
+        respondent_id: T,
+        rating: NpsRating,
+        timestamp: SystemTime,
+    ) -> Result<Self, NetPromoterScoreError> {
+        let mut response = Self::new(respondent_id, rating)?;
+        response.timestamp = Some(timestamp);
+        Ok(response)
+    }
+
+    /// Returns the response with the attribute `key` set to `value`, such as a region or plan
+    /// tier. Setting an existing key replaces its value.
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the response with all of the given attributes set.
+    pub fn with_attributes<K, V>(mut self, attributes: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.attributes.extend(
+            attributes
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+        self
+    }
+
+    /// Returns the response with a free-text comment, such as the respondent's answer to "why did
+    /// you give that score?". Setting a comment replaces any earlier one.
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Returns the time the response was given, if known.
+    pub fn timestamp(&self) -> Option<SystemTime> {
+        self.timestamp
+    }
+
+    /// Returns the value of the attribute `key`, if the response has it.
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.get(key).map(String::as_str)
+    }
+
+    /// Returns all attributes of the response, ordered by key.
+    pub fn attributes(&self) -> &BTreeMap<String, String> {
+        &self.attributes
+    }
+
+    /// Returns the free-text comment left with the response, if any.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
     /// Returns the respondent ID of the survey response.
     pub fn respondent_id(&self) -> &T {
         &self.respondent_id
@@ -720,15 +1989,28 @@ impl Display for Rating {
 }
 
 /// Classification of survey respondents, based on their score, into Detractor, Passive, and Promoter.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Classification {
     Detractor,
     Passive,
     Promoter,
 }
 
+// Implementing Display for Classification to allow printing the segment name.
+impl Display for Classification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
 /// The `Rating` represents a valid survey response score in the range of 0 to 10.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u8", into = "u8")
+)]
 pub struct Rating(u8);
 
 // Conversion from a Rating to a Classification.
@@ -768,6 +2050,12 @@ impl TryFrom<u8> for Rating {
     }
 }
 
+impl From<Rating> for u8 {
+    fn from(rating: Rating) -> Self {
+        rating.0
+    }
+}
+
 // Implementing the Deref trait for Rating, allowing users to access the inner u8 value.
 impl Deref for Rating {
     type Target = u8;
@@ -778,9 +2066,33 @@ impl Deref for Rating {
 }
 
 /// Errors that may occur when working with the Net Promoter Score.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NetPromoterScoreError {
     InvalidRating(u8),
+    /// A confidence level outside the open interval (0, 1) was requested.
+    InvalidConfidenceLevel,
+    /// A statistic was requested for too few responses; holds the number of responses available.
+    InsufficientResponses(ScoreCount),
+    /// A significance level outside the open interval (0, 1) was requested.
+    InvalidSignificanceLevel,
+    /// A response was added for a respondent who already has one, under
+    /// [`DuplicatePolicy::Reject`].
+    DuplicateRespondent,
+    /// A rating lies outside the bounds of a custom [`ClassificationScheme`].
+    RatingOutOfRange {
+        /// The rating that was given.
+        rating: u8,
+        /// The lowest rating on the scale.
+        min: u8,
+        /// The highest rating on the scale.
+        max: u8,
+    },
+    /// A [`Scale`](scale::Scale) was given thresholds that are out of order or above 10.
+    InvalidScale,
+    /// A response without a timestamp was added to a [`Panel`](panel::Panel), which orders each
+    /// respondent's responses by time.
+    MissingTimestamp,
 }
 
 // Implementing the Error trait for NetPromoterScoreError.
@@ -791,7 +2103,39 @@ impl std::fmt::Display for NetPromoterScoreError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             NetPromoterScoreError::InvalidRating(value) => {
-                write!(f, "Invalid rating value: {}", value)
+                write!(f, "Invalid rating value: {} (expected 0 to 10)", value)
+            }
+            NetPromoterScoreError::RatingOutOfRange { rating, min, max } => {
+                write!(
+                    f,
+                    "Rating {} is outside the scale {} to {}",
+                    rating, min, max
+                )
+            }
+            NetPromoterScoreError::InvalidScale => {
+                write!(
+                    f,
+                    "Invalid scale: expected min <= passive <= promoter <= max <= 10"
+                )
+            }
+            NetPromoterScoreError::InvalidConfidenceLevel => {
+                write!(f, "Invalid confidence level (expected 0 < level < 1)")
+            }
+            NetPromoterScoreError::InsufficientResponses(count) => {
+                write!(
+                    f,
+                    "Not enough responses to compute the statistic: {}",
+                    count
+                )
+            }
+            NetPromoterScoreError::DuplicateRespondent => {
+                write!(f, "Duplicate respondent")
+            }
+            NetPromoterScoreError::MissingTimestamp => {
+                write!(f, "Response has no timestamp")
+            }
+            NetPromoterScoreError::InvalidSignificanceLevel => {
+                write!(f, "Invalid significance level (expected 0 < alpha < 1)")
             }
         }
     }
@@ -809,8 +2153,80 @@ mod tests {
             .add_multiple_responses(vec![(1, 10), (2, 9), (3, 9), (4, 8), (5, 7), (6, 6)])
             .unwrap();
 
+        // 50% promoters minus 16.67% detractors is 33.33, which rounds down
         let nps = survey.score();
-        assert_eq!(nps, 34);
+        assert_eq!(nps, 33);
+    }
+    #[test]
+    fn test_exact_nps_calculation() {
+        let mut survey = Survey::new();
+        survey
+            .add_multiple_responses(vec![(1, 10), (2, 9), (3, 9), (4, 8), (5, 7), (6, 6)])
+            .unwrap();
+
+        assert!((survey.score_exact() - 100.0 / 3.0).abs() < 1e-9);
+    }
+    #[test]
+    fn test_rounding_applied_once_to_final_score() {
+        // 2 promoters, 1 passive and 4 detractors: truncating each percentage first
+        // gives 28 - 57 = -29, while the exact score is -28.57.
+        let mut survey = Survey::new();
+        survey
+            .add_multiple_responses(vec![
+                (1, 9),
+                (2, 10),
+                (3, 7),
+                (4, 0),
+                (5, 1),
+                (6, 2),
+                (7, 3),
+            ])
+            .unwrap();
+
+        assert!((survey.score_exact() - (-200.0 / 7.0)).abs() < 1e-9);
+        assert_eq!(survey.score_with(RoundingPolicy::Truncate), -28);
+        assert_eq!(survey.score_with(RoundingPolicy::HalfUp), -29);
+        assert_eq!(survey.score_with(RoundingPolicy::HalfEven), -29);
+    }
+    #[test]
+    fn test_rounding_policies_on_halfway_scores() {
+        // 1 promoter and 7 passives is exactly 12.5; 5 promoters and 3 passives is 62.5
+        let mut low = Survey::new();
+        low.add_multiple_responses((1..=8).map(|id| (id, if id == 1 { 10 } else { 8 })))
+            .unwrap();
+        let mut high = Survey::new();
+        high.add_multiple_responses((1..=8).map(|id| (id, if id <= 5 { 9 } else { 7 })))
+            .unwrap();
+
+        assert_eq!(low.score_with(RoundingPolicy::Truncate), 12);
+        assert_eq!(low.score_with(RoundingPolicy::HalfUp), 13);
+        assert_eq!(low.score_with(RoundingPolicy::HalfEven), 12);
+        assert_eq!(high.score_with(RoundingPolicy::Truncate), 62);
+        assert_eq!(high.score_with(RoundingPolicy::HalfUp), 63);
+        assert_eq!(high.score_with(RoundingPolicy::BANKERS), 62);
+
+        low.set_rounding_policy(RoundingPolicy::Truncate);
+        assert_eq!(low.score(), 12);
+    }
+    #[test]
+    fn test_rounding_policy_negative_halves() {
+        assert_eq!(RoundingPolicy::Truncate.round(-37.5), -37.0);
+        assert_eq!(RoundingPolicy::HalfUp.round(-37.5), -38.0);
+        assert_eq!(RoundingPolicy::HalfEven.round(-37.5), -38.0);
+        assert_eq!(RoundingPolicy::HalfEven.round(-36.5), -36.0);
+    }
+    #[test]
+    fn test_score_not_stale_after_add_response() -> Result<(), Error> {
+        let mut survey = Survey::new();
+        survey.add_response(1, 10)?;
+        assert_eq!(survey.score(), 100);
+
+        survey.add_response(2, 0)?;
+        assert_eq!(survey.score(), 0);
+
+        survey.extend(vec![(3, SurveyResponse::new(3, 0)?)]);
+        assert_eq!(survey.score(), -33);
+        Ok(())
     }
     #[test]
     fn test_create_survey_with_add_multiple_responses() {
@@ -854,6 +2270,250 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_score_between_ignores_untimed_responses() -> Result<(), Error> {
+        use std::time::{Duration, UNIX_EPOCH};
+        let at = |seconds| UNIX_EPOCH + Duration::from_secs(seconds);
+
+        let mut survey = Survey::new();
+        survey.add_response_at(1, 10, at(100))?;
+        survey.add_response_at(2, 9, at(200))?;
+        survey.add_response_at(3, 2, at(300))?;
+        survey.add_response(4, 0)?;
+
+        assert_eq!(survey.score_between(at(100)..at(300)), 100);
+        assert_eq!(survey.score_between(at(100)..=at(300)), 33);
+        assert_eq!(survey.score_between(at(400)..), 0);
+        assert_eq!(survey.segment_counts_between(..).total(), 3);
+        assert_eq!(survey.score(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_group_by_attributes() -> Result<(), Error> {
+        let mut survey = Survey::new();
+        let rows = [
+            (1, 10, "EMEA", "pro"),
+            (2, 9, "EMEA", "pro"),
+            (3, 4, "EMEA", "free"),
+            (4, 8, "APAC", "free"),
+            (5, 0, "APAC", "free"),
+            (6, 9, "AMER", "pro"),
+        ];
+        for (respondent_id, score, region, plan) in rows {
+            survey.add_response_with_attributes(
+                respondent_id,
+                score,
+                [("region", region), ("plan", plan)],
+            )?;
+        }
+        survey.add_response_with_attributes(7, 0, [("plan", "free")])?;
+
+        let regions = survey.group_by("region");
+        assert_eq!(
+            regions.keys().collect::<Vec<_>>(),
+            vec!["AMER", "APAC", "EMEA"]
+        );
+        assert_eq!(
+            regions["EMEA"],
+            SegmentCounts {
+                detractors: 1,
+                passives: 0,
+                promoters: 2
+            }
+        );
+        assert_eq!(regions["APAC"].score_exact(), -50.0);
+        assert!(regions["EMEA"]
+            .confidence_interval(0.95, IntervalMethod::Trinomial)
+            .is_ok());
+
+        let plans = survey.group_by("plan");
+        assert_eq!(plans["free"].total(), 4);
+        assert!(survey.group_by("channel").is_empty());
+
+        let combined = survey.group_by_keys(&["region", "plan"]);
+        assert_eq!(combined.len(), 4);
+        assert_eq!(
+            combined[&vec!["APAC".to_string(), "free".to_string()]].total(),
+            2
+        );
+
+        let pivot = survey.pivot("plan", "region");
+        assert_eq!(pivot["pro"].len(), 2);
+        assert_eq!(pivot["free"]["EMEA"].detractors, 1);
+        assert!(!pivot["free"].contains_key("AMER"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_histogram_tracks_replacements() -> Result<(), Error> {
+        let mut survey = Survey::new();
+        survey
+            .add_multiple_responses(vec![(1, 10), (2, 3), (3, 8)])
+            .unwrap();
+        survey.add_response(2, 9)?;
+        survey.extend(vec![
+            (3, SurveyResponse::new(3, 0)?),
+            (4, SurveyResponse::new(4, 7)?),
+        ]);
+
+        let recounted: ScoreHistogram = survey
+            .responses()
+            .map(|response| *response.score())
+            .collect();
+        assert_eq!(survey.histogram(), &recounted);
+        assert_eq!(survey.histogram().total(), 4);
+        assert_eq!(
+            survey.segment_counts(),
+            SegmentCounts {
+                detractors: 1,
+                passives: 1,
+                promoters: 2
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_policies() -> Result<(), Error> {
+        let responses = vec![(1, 10), (2, 3), (1, 0), (3, 8), (1, 7)];
+        let survey_with = |policy| {
+            let mut survey = Survey::new();
+            survey.set_duplicate_policy(policy);
+            let result = survey.add_multiple_responses(responses.clone());
+            (survey, result)
+        };
+
+        let (keep_first, duplicates) = survey_with(DuplicatePolicy::KeepFirst);
+        assert_eq!(duplicates.unwrap(), vec![1, 1]);
+        assert_eq!(**keep_first.responses().next().unwrap().score(), 10);
+        assert_eq!(keep_first.score(), 0);
+
+        let (keep_last, duplicates) = survey_with(DuplicatePolicy::KeepLast);
+        assert_eq!(duplicates.unwrap(), vec![1, 1]);
+        assert_eq!(**keep_last.responses().next().unwrap().score(), 7);
+        assert_eq!(keep_last.score(), -33);
+        assert!(keep_last.history(&1).is_empty());
+
+        let (reject, error) = survey_with(DuplicatePolicy::Reject);
+        let error = error.unwrap_err();
+        let rejected: Vec<_> = error
+            .rejected
+            .iter()
+            .map(|entry| (entry.index, entry.respondent_id))
+            .collect();
+        assert_eq!(rejected, vec![(2, 1), (4, 1)]);
+        assert!(error
+            .errors()
+            .all(|reason| *reason == NetPromoterScoreError::DuplicateRespondent));
+        assert!(error.duplicates.is_empty());
+        assert_eq!(reject.responses().count(), 3);
+        assert_eq!(reject.score(), 0);
+
+        let (mut keep_all, duplicates) = survey_with(DuplicatePolicy::KeepAll);
+        assert_eq!(duplicates.unwrap(), vec![1, 1]);
+        let history: Vec<u8> = keep_all
+            .history(&1)
+            .iter()
+            .map(|response| **response.score())
+            .collect();
+        assert_eq!(history, vec![10, 0]);
+        assert_eq!(keep_all.responses().count(), 3);
+        assert_eq!(keep_all.score(), -33);
+
+        assert_eq!(keep_all.add_response(4, 9)?, None);
+        assert_eq!(keep_all.add_response(4, 10)?, Some(4));
+        Ok(())
+    }
+
+    #[test]
+    fn test_batch_errors_keep_duplicates() {
+        let mut survey = Survey::new();
+        let error = survey
+            .add_multiple_responses(vec![("a", 10), ("b", 12), ("a", 3), ("c", 7)])
+            .unwrap_err();
+        assert_eq!(error.duplicates, vec!["a"]);
+        assert_eq!(
+            error.rejected,
+            vec![RejectedEntry {
+                index: 1,
+                respondent_id: "b",
+                reason: NetPromoterScoreError::InvalidRating(12)
+            }]
+        );
+        assert_eq!(
+            error.to_string(),
+            "1 entries could not be added: entry 1 (respondent b): Invalid rating value: 12 (expected 0 to 10)"
+        );
+        assert_eq!(survey.responses().count(), 2);
+
+        let mut shard = Survey::new();
+        shard
+            .add_multiple_responses(vec![("c", 9), ("d", 9)])
+            .unwrap();
+        survey.set_duplicate_policy(DuplicatePolicy::Reject);
+        let error = survey.merge(shard).unwrap_err();
+        assert_eq!(error.rejected[0].respondent_id, "c");
+        assert_eq!(
+            error.errors().collect::<Vec<_>>(),
+            vec![&NetPromoterScoreError::DuplicateRespondent]
+        );
+        assert_eq!(survey.responses().count(), 3);
+    }
+
+    #[test]
+    fn test_extend_skips_rejected_duplicates() -> Result<(), Error> {
+        let mut survey = Survey::new();
+        survey.set_duplicate_policy(DuplicatePolicy::Reject);
+        survey.add_response(1, 10)?;
+        survey.extend(vec![
+            (1, SurveyResponse::new(1, 0)?),
+            (2, SurveyResponse::new(2, 9)?),
+        ]);
+
+        assert_eq!(survey.responses().count(), 2);
+        assert_eq!(survey.score(), 100);
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_update_retain_erase() -> Result<(), Error> {
+        let mut survey = Survey::new();
+        survey.set_duplicate_policy(DuplicatePolicy::KeepAll);
+        survey
+            .add_multiple_responses(vec![(1, 10), (2, 9), (3, 5), (4, 8), (1, 6)])
+            .unwrap();
+        assert_eq!(survey.len(), 4);
+        assert_eq!(survey.score(), -25);
+
+        assert_eq!(survey.update_response(&3, 10)?, Some(Rating(5)));
+        assert_eq!(survey.history(&3), [SurveyResponse::new(3, 5)?]);
+        assert_eq!(survey.update_response(&9, 10)?, None);
+        assert!(survey.history(&9).is_empty());
+        assert_eq!(
+            survey.update_response(&3, 11),
+            Err(NetPromoterScoreError::InvalidRating(11))
+        );
+        assert_eq!(survey.score(), 25);
+
+        let removed = survey.remove_response(&1).unwrap();
+        assert_eq!(*removed.score(), Rating(6));
+        assert!(survey.history(&1).is_empty());
+        assert_eq!(survey.score(), 67);
+
+        survey.retain(|response| **response.score() >= 9);
+        assert_eq!(survey.len(), 2);
+        assert_eq!(survey.score(), 100);
+
+        // Respondent 3's pre-update response is erased along with the current ones.
+        let erased = survey.erase_respondents(&[2, 3, 5]);
+        assert_eq!(erased.len(), 3);
+        assert!(survey.is_empty());
+        assert_eq!(survey.score(), 0);
+        assert_eq!(survey.histogram().total(), 0);
+        Ok(())
+    }
+
     #[test]
     fn test_survey_response() -> Result<(), Error> {
         let response = SurveyResponse::new(1, 7)?;
@@ -899,7 +2559,7 @@ mod tests {
         match survey_result {
             Ok(ref mut survey) => {
                 assert_eq!(survey.responses.values().count(), 6);
-                assert_eq!(survey.score(), 34);
+                assert_eq!(survey.score(), 33);
             }
             Err(errors) => panic!("Unexpected errors while parsing responses: {:?}", errors),
         }
@@ -1016,7 +2676,7 @@ mod tests {
         assert_eq!(nps, 15);
     }
     #[test]
-    fn test_add_bulk_responses_auto_id() -> Result<(), Vec<NetPromoterScoreError>> {
+    fn test_add_bulk_responses_auto_id() -> Result<(), BatchError<i32>> {
         let rating_quantities = [(1, 2), (4, 1), (5, 2), (7, 8), (8, 10), (10, 10)];
 
         let mut survey = Survey::new();
@@ -0,0 +1,284 @@
+//! Customer metrics computed from the same responses as the Net Promoter Score.
+//!
+//! A [`Metric`] is evaluated against the rating counts in a [`ScoreHistogram`] and the survey's
+//! [`ClassificationScheme`], so every metric shares one ingestion path, one response store and the
+//! same attribute segmentation through [`Survey::evaluate`](crate::Survey::evaluate) and
+//! [`Survey::evaluate_by`](crate::Survey::evaluate_by).
+//!
+//! | Metric        | Value                                                        |
+//! |---------------|--------------------------------------------------------------|
+//! | [`Nps`]       | Percentage of promoters minus percentage of detractors       |
+//! | [`Csat`]      | Percentage of responses in the top two ratings of the scale  |
+//! | [`Ces`]       | Mean Customer Effort Score                                   |
+//! | [`Mean`]      | Mean rating                                                  |
+//! | [`Median`]    | Median rating                                                |
+//! | [`TopBox`]    | Percentage of responses in the highest ratings of the scale  |
+//! | [`BottomBox`] | Percentage of responses in the lowest ratings of the scale   |
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//!
+//! // A CSAT survey on a 1 to 5 scale
+//! let mut survey = Survey::with_scheme(Scale::new(1, 5, 3, 4).unwrap());
+//! survey.add_multiple_responses(vec![(1, 5), (2, 4), (3, 4), (4, 2)]).unwrap();
+//!
+//! assert_eq!(survey.evaluate(&Csat).unwrap(), 75.0);
+//! assert_eq!(survey.evaluate(&Mean).unwrap(), 3.75);
+//! assert_eq!(survey.evaluate(&BottomBox(2)).unwrap(), 25.0);
+//! ```
+
+use crate::histogram::ScoreHistogram;
+use crate::scale::ClassificationScheme;
+use crate::{NetPromoterScoreError, NpsRating, ScoreCount};
+
+/// A single figure computed from the ratings of a survey.
+///
+/// Implementations read the per-rating counts of a [`ScoreHistogram`] and may use the survey's
+/// [`ClassificationScheme`] for its bounds or its classification of ratings.
+///
+/// # Example
+///
+/// ```
+/// use net_promoter_score::prelude::*;
+///
+/// // The share of responses rated exactly 10
+/// struct PerfectTens;
+///
+/// impl Metric for PerfectTens {
+///     fn evaluate(
+///         &self,
+///         histogram: &ScoreHistogram,
+///         _scheme: &dyn ClassificationScheme,
+///     ) -> Result<f64, NetPromoterScoreError> {
+///         match histogram.total() {
+///             0 => Err(NetPromoterScoreError::InsufficientResponses(0)),
+///             total => Ok(100.0 * histogram.count(10) as f64 / total as f64),
+///         }
+///     }
+/// }
+///
+/// let mut survey = Survey::new();
+/// survey.add_multiple_responses(vec![(1, 10), (2, 9)]).unwrap();
+/// assert_eq!(survey.evaluate(&PerfectTens).unwrap(), 50.0);
+/// ```
+pub trait Metric {
+    /// Returns the value of the metric for the ratings counted in `histogram`.
+    ///
+    /// # Errors
+    ///
+    /// The metrics in this module return `NetPromoterScoreError::InsufficientResponses` if
+    /// `histogram` is empty.
+    fn evaluate(
+        &self,
+        histogram: &ScoreHistogram,
+        scheme: &dyn ClassificationScheme,
+    ) -> Result<f64, NetPromoterScoreError>;
+}
+
+/// The Net Promoter Score, from -100 to 100, classified with the survey's scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Nps;
+
+impl Metric for Nps {
+    fn evaluate(
+        &self,
+        histogram: &ScoreHistogram,
+        scheme: &dyn ClassificationScheme,
+    ) -> Result<f64, NetPromoterScoreError> {
+        require_responses(histogram)?;
+        Ok(histogram.segment_counts_with(&scheme).score_exact())
+    }
+}
+
+/// The Customer Satisfaction Score: the percentage of responses in the top two ratings of the
+/// scale, such as 4 and 5 on a 1 to 5 scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Csat;
+
+impl Metric for Csat {
+    fn evaluate(
+        &self,
+        histogram: &ScoreHistogram,
+        scheme: &dyn ClassificationScheme,
+    ) -> Result<f64, NetPromoterScoreError> {
+        TopBox(2).evaluate(histogram, scheme)
+    }
+}
+
+/// The Customer Effort Score: the mean rating, usually on a 1 to 7 scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Ces;
+
+impl Metric for Ces {
+    fn evaluate(
+        &self,
+        histogram: &ScoreHistogram,
+        scheme: &dyn ClassificationScheme,
+    ) -> Result<f64, NetPromoterScoreError> {
+        Mean.evaluate(histogram, scheme)
+    }
+}
+
+/// The mean rating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Mean;
+
+impl Metric for Mean {
+    fn evaluate(
+        &self,
+        histogram: &ScoreHistogram,
+        _scheme: &dyn ClassificationScheme,
+    ) -> Result<f64, NetPromoterScoreError> {
+        let total = require_responses(histogram)?;
+        let sum: ScoreCount = ratings(histogram)
+            .map(|(rating, count)| rating as ScoreCount * count)
+            .sum();
+        Ok(sum as f64 / total as f64)
+    }
+}
+
+/// The median rating. With an even number of responses, the mean of the two middle ratings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Median;
+
+impl Metric for Median {
+    fn evaluate(
+        &self,
+        histogram: &ScoreHistogram,
+        _scheme: &dyn ClassificationScheme,
+    ) -> Result<f64, NetPromoterScoreError> {
+        let total = require_responses(histogram)?;
+        // The 0-based positions of the middle response or responses in rating order
+        let (lower, upper) = ((total - 1) / 2, total / 2);
+        Ok((rating_at(histogram, lower) + rating_at(histogram, upper)) as f64 / 2.0)
+    }
+}
+
+/// The percentage of responses in the highest `n` ratings of the scale, such as top-2-box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopBox(pub NpsRating);
+
+impl Metric for TopBox {
+    fn evaluate(
+        &self,
+        histogram: &ScoreHistogram,
+        scheme: &dyn ClassificationScheme,
+    ) -> Result<f64, NetPromoterScoreError> {
+        let lowest = scheme.max_rating().saturating_add(1).saturating_sub(self.0);
+        share(histogram, |rating| rating >= lowest)
+    }
+}
+
+/// The percentage of responses in the lowest `n` ratings of the scale, such as bottom-2-box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BottomBox(pub NpsRating);
+
+impl Metric for BottomBox {
+    fn evaluate(
+        &self,
+        histogram: &ScoreHistogram,
+        scheme: &dyn ClassificationScheme,
+    ) -> Result<f64, NetPromoterScoreError> {
+        let highest = scheme.min_rating().saturating_add(self.0);
+        share(histogram, |rating| rating < highest)
+    }
+}
+
+fn require_responses(histogram: &ScoreHistogram) -> Result<ScoreCount, NetPromoterScoreError> {
+    match histogram.total() {
+        0 => Err(NetPromoterScoreError::InsufficientResponses(0)),
+        total => Ok(total),
+    }
+}
+
+// Iterates over each rating and its count.
+fn ratings(histogram: &ScoreHistogram) -> impl Iterator<Item = (NpsRating, ScoreCount)> + '_ {
+    (0..).zip(histogram.counts().iter().copied())
+}
+
+// Returns the rating of the response at `position` when all responses are sorted by rating.
+fn rating_at(histogram: &ScoreHistogram, position: ScoreCount) -> NpsRating {
+    let mut seen = 0;
+    for (rating, count) in ratings(histogram) {
+        seen += count;
+        if seen > position {
+            return rating;
+        }
+    }
+    10
+}
+
+// Returns the percentage of responses whose rating satisfies `in_box`.
+fn share(
+    histogram: &ScoreHistogram,
+    in_box: impl Fn(NpsRating) -> bool,
+) -> Result<f64, NetPromoterScoreError> {
+    let total = require_responses(histogram)?;
+    let count: ScoreCount = ratings(histogram)
+        .filter(|&(rating, _)| in_box(rating))
+        .map(|(_, count)| count)
+        .sum();
+    Ok(100.0 * count as f64 / total as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::{NpsScheme, Scale};
+    use crate::Rating;
+    use std::convert::TryFrom;
+
+    fn histogram(ratings: &[NpsRating]) -> ScoreHistogram {
+        let mut histogram = ScoreHistogram::new();
+        for &rating in ratings {
+            histogram.insert(Rating::try_from(rating).unwrap());
+        }
+        histogram
+    }
+
+    #[test]
+    fn test_mean_and_median() {
+        let odd = histogram(&[1, 2, 7, 7, 3]);
+        assert_eq!(Mean.evaluate(&odd, &NpsScheme), Ok(4.0));
+        assert_eq!(Median.evaluate(&odd, &NpsScheme), Ok(3.0));
+
+        let even = histogram(&[1, 2, 7, 7]);
+        assert_eq!(Median.evaluate(&even, &NpsScheme), Ok(4.5));
+        assert_eq!(Ces.evaluate(&even, &NpsScheme), Ok(4.25));
+    }
+
+    #[test]
+    fn test_boxes_follow_scale_bounds() {
+        let csat = Scale::new(1, 5, 3, 4).unwrap();
+        let responses = histogram(&[1, 2, 3, 4, 5]);
+        assert_eq!(Csat.evaluate(&responses, &csat), Ok(40.0));
+        assert_eq!(TopBox(1).evaluate(&responses, &csat), Ok(20.0));
+        assert_eq!(BottomBox(2).evaluate(&responses, &csat), Ok(40.0));
+        assert_eq!(BottomBox(2).evaluate(&responses, &NpsScheme), Ok(20.0));
+        assert_eq!(TopBox(0).evaluate(&responses, &csat), Ok(0.0));
+        assert_eq!(TopBox(20).evaluate(&responses, &csat), Ok(100.0));
+    }
+
+    #[test]
+    fn test_nps_uses_scheme() {
+        let responses = histogram(&[10, 9, 8, 3]);
+        assert_eq!(Nps.evaluate(&responses, &NpsScheme), Ok(25.0));
+        let strict = Scale::new(0, 10, 7, 10).unwrap();
+        assert_eq!(Nps.evaluate(&responses, &strict), Ok(0.0));
+    }
+
+    #[test]
+    fn test_empty_histogram() {
+        let empty = ScoreHistogram::new();
+        let metrics: [&dyn Metric; 7] =
+            [&Nps, &Csat, &Ces, &Mean, &Median, &TopBox(2), &BottomBox(2)];
+        for metric in metrics {
+            assert_eq!(
+                metric.evaluate(&empty, &NpsScheme),
+                Err(NetPromoterScoreError::InsufficientResponses(0))
+            );
+        }
+    }
+}
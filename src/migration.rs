@@ -0,0 +1,263 @@
+//! How respondents moved between classifications from one survey wave to the next.
+//!
+//! [`migration`] joins two surveys on respondent ID and returns a [`MigrationMatrix`]: a 3×3 table
+//! of how many respondents went from each [`Classification`] in the first wave to each
+//! classification in the second, with row percentages and the respondents in every cell. It also
+//! lists the respondents who answered only one of the waves, for retention and attrition.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::migration::migration;
+//! use net_promoter_score::prelude::*;
+//!
+//! let mut spring = Survey::new();
+//! spring.add_multiple_responses(vec![("ana", 3), ("ben", 10), ("cy", 9), ("dee", 5)]).unwrap();
+//! let mut autumn = Survey::new();
+//! autumn.add_multiple_responses(vec![("ana", 9), ("ben", 8), ("cy", 10), ("eve", 2)]).unwrap();
+//!
+//! let matrix = migration(&spring, &autumn);
+//! use Classification::*;
+//! assert_eq!(matrix.respondents(Detractor, Promoter), ["ana"]);
+//! assert_eq!(matrix.count(Promoter, Passive), 1);
+//! assert_eq!(matrix.row_percentage(Promoter, Promoter), 50.0);
+//! assert_eq!(matrix.retained(), 3);
+//! assert_eq!(matrix.only_before(), ["dee"]);
+//! assert_eq!(matrix.only_after(), ["eve"]);
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::scale::ClassificationScheme;
+use crate::{Classification, Survey};
+
+const CLASSIFICATIONS: [Classification; 3] = [
+    Classification::Detractor,
+    Classification::Passive,
+    Classification::Promoter,
+];
+
+/// Respondent movements between the classifications of two survey waves, as returned by
+/// [`migration`].
+///
+/// Rows are the classification in the first wave and columns the classification in the second.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MigrationMatrix<T> {
+    cells: [[Vec<T>; 3]; 3],
+    only_before: Vec<T>,
+    only_after: Vec<T>,
+}
+
+impl<T> MigrationMatrix<T> {
+    /// Returns the respondents classified as `from` in the first wave and as `to` in the second,
+    /// in respondent ID order.
+    pub fn respondents(&self, from: Classification, to: Classification) -> &[T] {
+        &self.cells[index(from)][index(to)]
+    }
+
+    /// Returns the number of respondents classified as `from` in the first wave and as `to` in
+    /// the second.
+    pub fn count(&self, from: Classification, to: Classification) -> usize {
+        self.respondents(from, to).len()
+    }
+
+    /// Returns the number of respondents in both waves who were classified as `from` in the first.
+    pub fn row_total(&self, from: Classification) -> usize {
+        self.cells[index(from)].iter().map(Vec::len).sum()
+    }
+
+    /// Returns the percentage, from 0 to 100, of respondents classified as `from` in the first
+    /// wave who were classified as `to` in the second, or `0.0` if none were classified as `from`.
+    pub fn row_percentage(&self, from: Classification, to: Classification) -> f64 {
+        match self.row_total(from) {
+            0 => 0.0,
+            total => 100.0 * self.count(from, to) as f64 / total as f64,
+        }
+    }
+
+    /// Returns the number of respondents who answered both waves.
+    pub fn retained(&self) -> usize {
+        CLASSIFICATIONS
+            .iter()
+            .map(|&from| self.row_total(from))
+            .sum()
+    }
+
+    /// Returns the number of respondents in both waves whose classification went up, e.g. from
+    /// detractor to passive or promoter.
+    pub fn improved(&self) -> usize {
+        self.movements()
+            .filter(|(from, to, _)| to > from)
+            .map(|(_, _, count)| count)
+            .sum()
+    }
+
+    /// Returns the number of respondents in both waves whose classification went down.
+    pub fn declined(&self) -> usize {
+        self.movements()
+            .filter(|(from, to, _)| to < from)
+            .map(|(_, _, count)| count)
+            .sum()
+    }
+
+    /// Returns the respondents who answered only the first wave, in respondent ID order.
+    pub fn only_before(&self) -> &[T] {
+        &self.only_before
+    }
+
+    /// Returns the respondents who answered only the second wave, in respondent ID order.
+    pub fn only_after(&self) -> &[T] {
+        &self.only_after
+    }
+
+    /// Returns the share, from 0 to 1, of first-wave respondents who also answered the second
+    /// wave, or `0.0` if the first wave was empty.
+    pub fn retention_rate(&self) -> f64 {
+        match self.retained() + self.only_before.len() {
+            0 => 0.0,
+            total => self.retained() as f64 / total as f64,
+        }
+    }
+
+    fn movements(&self) -> impl Iterator<Item = (Classification, Classification, usize)> + '_ {
+        CLASSIFICATIONS.iter().flat_map(move |&from| {
+            CLASSIFICATIONS
+                .iter()
+                .map(move |&to| (from, to, self.count(from, to)))
+        })
+    }
+}
+
+// Prints the counts and row percentages as a table, followed by the respondents in only one wave.
+impl<T> Display for MigrationMatrix<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<10}", "From \\ To")?;
+        for to in CLASSIFICATIONS {
+            write!(f, " {:>15}", to.to_string())?;
+        }
+        write!(f, " {:>6}", "Total")?;
+        for from in CLASSIFICATIONS {
+            write!(f, "\n{:<10}", from.to_string())?;
+            for to in CLASSIFICATIONS {
+                let cell = format!(
+                    "{} ({:.1}%)",
+                    self.count(from, to),
+                    self.row_percentage(from, to)
+                );
+                write!(f, " {:>15}", cell)?;
+            }
+            write!(f, " {:>6}", self.row_total(from))?;
+        }
+        write!(
+            f,
+            "\nRetained {}, only before {}, only after {}",
+            self.retained(),
+            self.only_before.len(),
+            self.only_after.len()
+        )
+    }
+}
+
+/// Joins two survey waves on respondent ID and counts how respondents moved between
+/// classifications.
+///
+/// Each wave classifies its own responses with its own scheme, and only the current response of
+/// each respondent is used.
+pub fn migration<T, SA, SB>(before: &Survey<T, SA>, after: &Survey<T, SB>) -> MigrationMatrix<T>
+where
+    T: PartialEq + Ord + Clone,
+    SA: ClassificationScheme,
+    SB: ClassificationScheme,
+{
+    let mut matrix = MigrationMatrix {
+        cells: Default::default(),
+        only_before: Vec::new(),
+        only_after: Vec::new(),
+    };
+    for (respondent_id, first) in &before.responses {
+        match after.responses.get(respondent_id) {
+            Some(second) => {
+                let from = before.classify(first.score);
+                let to = after.classify(second.score);
+                matrix.cells[index(from)][index(to)].push(respondent_id.clone());
+            }
+            None => matrix.only_before.push(respondent_id.clone()),
+        }
+    }
+    matrix.only_after = after
+        .responses
+        .keys()
+        .filter(|respondent_id| !before.responses.contains_key(respondent_id))
+        .cloned()
+        .collect();
+    matrix
+}
+
+fn index(classification: Classification) -> usize {
+    match classification {
+        Classification::Detractor => 0,
+        Classification::Passive => 1,
+        Classification::Promoter => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::Scale;
+    use Classification::*;
+
+    #[test]
+    fn test_migration() {
+        let before = Survey::from_responses::<crate::NetPromoterScoreError>(
+            (1..=8).zip([0, 0, 0, 7, 7, 10, 10, 9]),
+        )
+        .unwrap();
+        let after = Survey::from_responses::<crate::NetPromoterScoreError>(
+            (2..=10).zip([9, 5, 10, 8, 8, 7, 3, 10, 10]),
+        )
+        .unwrap();
+
+        let matrix = migration(&before, &after);
+        assert_eq!(matrix.respondents(Detractor, Promoter), [2]);
+        assert_eq!(matrix.respondents(Detractor, Detractor), [3]);
+        assert_eq!(matrix.respondents(Passive, Passive), [5]);
+        assert_eq!(matrix.respondents(Promoter, Passive), [6, 7]);
+        assert_eq!(matrix.respondents(Promoter, Detractor), [8]);
+        assert_eq!(matrix.respondents(Passive, Promoter), [4]);
+        assert_eq!(matrix.count(Passive, Detractor), 0);
+        assert_eq!(matrix.row_total(Promoter), 3);
+        assert!((matrix.row_percentage(Promoter, Passive) - 200.0 / 3.0).abs() < 1e-9);
+        assert_eq!(matrix.row_percentage(Passive, Detractor), 0.0);
+        assert_eq!((matrix.improved(), matrix.declined()), (2, 3));
+        assert_eq!(matrix.retained(), 7);
+        assert_eq!(matrix.only_before(), [1]);
+        assert_eq!(matrix.only_after(), [9, 10]);
+        assert_eq!(matrix.retention_rate(), 7.0 / 8.0);
+
+        let expected = "\
+From \\ To        Detractor         Passive        Promoter  Total
+Detractor        1 (50.0%)        0 (0.0%)       1 (50.0%)      2
+Passive           0 (0.0%)       1 (50.0%)       1 (50.0%)      2
+Promoter         1 (33.3%)       2 (66.7%)        0 (0.0%)      3
+Retained 7, only before 1, only after 2";
+        assert_eq!(matrix.to_string(), expected);
+    }
+
+    #[test]
+    fn test_migration_across_schemes() {
+        let mut before = Survey::with_scheme(Scale::new(1, 5, 4, 5).unwrap());
+        before.add_response("a", 4).unwrap();
+        let mut after = Survey::new();
+        after.add_response("a", 9).unwrap();
+
+        let matrix = migration(&before, &after);
+        assert_eq!(matrix.count(Passive, Promoter), 1);
+        assert_eq!(matrix.improved(), 1);
+
+        let empty = migration(&Survey::<u8>::new(), &Survey::<u8>::new());
+        assert_eq!(empty.retained(), 0);
+        assert_eq!(empty.retention_rate(), 0.0);
+    }
+}
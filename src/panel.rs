@@ -0,0 +1,387 @@
+//! Longitudinal panels that keep every timestamped response of each respondent.
+//!
+//! A [`Survey`] holds one current response per respondent. A [`Panel`] keeps every response of
+//! each respondent in time order. It can answer questions about how individual scores change: each
+//! respondent's latest score, their trajectory and trend, who dropped by several points, and
+//! the Net Promoter Score as of any moment, using each respondent's most recent answer before it.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! let day = |n: u64| UNIX_EPOCH + Duration::from_secs(n * 86_400);
+//!
+//! let mut panel = Panel::new();
+//! panel.add_response("ana", 10, day(1)).unwrap();
+//! panel.add_response("ben", 6, day(1)).unwrap();
+//! panel.add_response("ana", 4, day(30)).unwrap();
+//! panel.add_response("ben", 9, day(30)).unwrap();
+//!
+//! assert_eq!(panel.score_as_of(day(10)), 0);
+//! assert_eq!(panel.score_as_of(day(31)), 0);
+//! assert_eq!(**panel.latest(&"ana").unwrap().score(), 4);
+//!
+//! let drops = panel.score_drops(3);
+//! assert_eq!(drops.len(), 1);
+//! assert_eq!((drops[0].respondent_id, drops[0].drop), ("ana", 6));
+//! ```
+
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use crate::scale::{ClassificationScheme, NpsScheme};
+use crate::{
+    NetPromoterScoreError, NpsRating, Rating, RoundingPolicy, SegmentCounts, Survey, SurveyResponse,
+};
+
+/// Every timestamped response of a panel of respondents, in time order per respondent.
+#[derive(Debug, Clone)]
+pub struct Panel<T, S = NpsScheme> {
+    respondents: BTreeMap<T, Vec<SurveyResponse<T>>>,
+    scheme: S,
+    rounding_policy: RoundingPolicy,
+}
+
+impl<T: PartialEq + Ord + Clone> Panel<T> {
+    /// Creates a new empty panel using the standard [`NpsScheme`].
+    pub fn new() -> Self {
+        Self::with_scheme(NpsScheme)
+    }
+}
+
+impl<T: PartialEq + Ord + Clone> Default for Panel<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq + Ord + Clone, S: ClassificationScheme> Panel<T, S> {
+    /// Creates a new empty panel that validates and classifies ratings with `scheme`.
+    pub fn with_scheme(scheme: S) -> Self {
+        Self {
+            respondents: BTreeMap::new(),
+            scheme,
+            rounding_policy: Default::default(),
+        }
+    }
+
+    /// Sets the `RoundingPolicy` used by [`score_as_of`](Panel::score_as_of).
+    pub fn set_rounding_policy(&mut self, policy: RoundingPolicy) {
+        self.rounding_policy = policy;
+    }
+
+    /// Adds a response with the given respondent ID, score and timestamp to the respondent's
+    /// history.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rating is invalid or outside the panel's scale.
+    pub fn add_response(
+        &mut self,
+        respondent_id: T,
+        score: NpsRating,
+        timestamp: SystemTime,
+    ) -> Result<(), NetPromoterScoreError> {
+        self.scheme.rating(score)?;
+        self.add_survey_response(SurveyResponse::new_at(respondent_id, score, timestamp)?)
+    }
+
+    /// Adds an already validated `SurveyResponse` to its respondent's history. Responses given at
+    /// the same time are kept in the order they were added.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetPromoterScoreError::MissingTimestamp` if the response has no timestamp, or an
+    /// error if its rating lies outside the panel's scale.
+    pub fn add_survey_response(
+        &mut self,
+        response: SurveyResponse<T>,
+    ) -> Result<(), NetPromoterScoreError> {
+        self.scheme.rating(*response.score)?;
+        let timestamp = response
+            .timestamp
+            .ok_or(NetPromoterScoreError::MissingTimestamp)?;
+        let history = self
+            .respondents
+            .entry(response.respondent_id.clone())
+            .or_default();
+        let position = history.partition_point(|earlier| earlier.timestamp <= Some(timestamp));
+        history.insert(position, response);
+        Ok(())
+    }
+
+    /// Returns the number of respondents in the panel.
+    pub fn len(&self) -> usize {
+        self.respondents.len()
+    }
+
+    /// Returns `true` if the panel has no responses.
+    pub fn is_empty(&self) -> bool {
+        self.respondents.is_empty()
+    }
+
+    /// Iterates over the respondent IDs of the panel, in order.
+    pub fn respondents(&self) -> impl Iterator<Item = &T> {
+        self.respondents.keys()
+    }
+
+    /// Returns every response of `respondent_id`, oldest first. The slice is empty for unknown
+    /// respondents.
+    pub fn history(&self, respondent_id: &T) -> &[SurveyResponse<T>] {
+        self.respondents
+            .get(respondent_id)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the most recent response of `respondent_id`, if any.
+    pub fn latest(&self, respondent_id: &T) -> Option<&SurveyResponse<T>> {
+        self.history(respondent_id).last()
+    }
+
+    /// Iterates over every respondent with the rating of their most recent response.
+    pub fn latest_scores(&self) -> impl Iterator<Item = (&T, Rating)> {
+        self.respondents
+            .iter()
+            .filter_map(|(respondent_id, history)| Some((respondent_id, history.last()?.score)))
+    }
+
+    /// Returns how the score of `respondent_id` has moved over time, or `None` for unknown
+    /// respondents.
+    pub fn trend(&self, respondent_id: &T) -> Option<Trend> {
+        let history = self.history(respondent_id);
+        let (first, latest) = (history.first()?, history.last()?);
+        let origin = first.timestamp?;
+        let points: Vec<(f64, f64)> = history
+            .iter()
+            .filter_map(|response| {
+                let elapsed = response.timestamp?.duration_since(origin).ok()?;
+                Some((elapsed.as_secs_f64() / 86_400.0, *response.score as f64))
+            })
+            .collect();
+
+        // The least-squares slope of the scores against the days since the first response.
+        let n = points.len() as f64;
+        let mean_day = points.iter().map(|(day, _)| day).sum::<f64>() / n;
+        let mean_score = points.iter().map(|(_, score)| score).sum::<f64>() / n;
+        let spread: f64 = points.iter().map(|(day, _)| (day - mean_day).powi(2)).sum();
+        let slope_per_day = if spread > 0.0 {
+            points
+                .iter()
+                .map(|(day, score)| (day - mean_day) * (score - mean_score))
+                .sum::<f64>()
+                / spread
+        } else {
+            0.0
+        };
+
+        Some(Trend {
+            responses: history.len(),
+            first: first.score,
+            latest: latest.score,
+            change: *latest.score as i32 - *first.score as i32,
+            slope_per_day,
+        })
+    }
+
+    /// Returns the respondents whose latest rating is at least `min_drop` points below their
+    /// previous one, largest drop first. Ties are kept in respondent ID order. A `min_drop` of 0 is
+    /// treated as 1.
+    pub fn score_drops(&self, min_drop: u8) -> Vec<ScoreDrop<T>> {
+        let mut drops: Vec<_> = self
+            .respondents
+            .iter()
+            .filter_map(|(respondent_id, history)| {
+                let [.., previous, latest] = history.as_slice() else {
+                    return None;
+                };
+                let drop = previous.score.saturating_sub(*latest.score);
+                (drop >= min_drop.max(1)).then(|| ScoreDrop {
+                    respondent_id: respondent_id.clone(),
+                    previous: previous.score,
+                    latest: latest.score,
+                    drop,
+                })
+            })
+            .collect();
+        drops.sort_by_key(|drop| std::cmp::Reverse(drop.drop));
+        drops
+    }
+
+    /// Returns the number of detractors, passives and promoters counting each respondent's most
+    /// recent response given strictly before `time`. Respondents with no response before `time`
+    /// are left out.
+    pub fn segment_counts_as_of(&self, time: SystemTime) -> SegmentCounts {
+        let mut counts = SegmentCounts::default();
+        for response in self.responses_as_of(time) {
+            counts.record(self.scheme.classify(response.score));
+        }
+        counts
+    }
+
+    /// Returns the exact Net Promoter Score as of `time`, as described in
+    /// [`segment_counts_as_of`](Panel::segment_counts_as_of).
+    pub fn score_exact_as_of(&self, time: SystemTime) -> f64 {
+        self.segment_counts_as_of(time).score_exact()
+    }
+
+    /// Returns the Net Promoter Score as of `time`, rounded with the panel's `RoundingPolicy`.
+    pub fn score_as_of(&self, time: SystemTime) -> i32 {
+        self.rounding_policy.round(self.score_exact_as_of(time)) as i32
+    }
+
+    /// Returns an ordinary `Survey` holding each respondent's most recent response given strictly
+    /// before `time`.
+    pub fn survey_as_of(&self, time: SystemTime) -> Survey<T, S>
+    where
+        S: Clone,
+    {
+        self.survey_of(self.responses_as_of(time))
+    }
+
+    /// Returns an ordinary `Survey` holding each respondent's most recent response.
+    pub fn latest_survey(&self) -> Survey<T, S>
+    where
+        S: Clone,
+    {
+        self.survey_of(
+            self.respondents
+                .values()
+                .filter_map(|history| history.last()),
+        )
+    }
+
+    fn responses_as_of(&self, time: SystemTime) -> impl Iterator<Item = &SurveyResponse<T>> {
+        self.respondents.values().filter_map(move |history| {
+            let before = history.partition_point(|response| response.timestamp < Some(time));
+            before.checked_sub(1).map(|index| &history[index])
+        })
+    }
+
+    fn survey_of<'a>(&self, responses: impl Iterator<Item = &'a SurveyResponse<T>>) -> Survey<T, S>
+    where
+        T: 'a,
+        S: Clone,
+    {
+        let mut survey = Survey::with_scheme(self.scheme.clone());
+        survey.set_rounding_policy(self.rounding_policy);
+        for response in responses {
+            survey
+                .insert_response(response.respondent_id.clone(), response.clone())
+                .expect("panel ratings were validated with the same scheme");
+        }
+        survey
+    }
+}
+
+/// How one respondent's score has moved over time, as returned by [`Panel::trend`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trend {
+    /// The number of responses given.
+    pub responses: usize,
+    /// The rating of the first response.
+    pub first: Rating,
+    /// The rating of the most recent response.
+    pub latest: Rating,
+    /// The latest rating minus the first one.
+    pub change: i32,
+    /// The least-squares slope of the ratings, in points per day. It is `0.0` when all responses
+    /// were given at the same time.
+    pub slope_per_day: f64,
+}
+
+/// A respondent whose latest rating fell below their previous one, as returned by
+/// [`Panel::score_drops`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreDrop<T> {
+    /// The respondent ID.
+    pub respondent_id: T,
+    /// The rating of the previous response.
+    pub previous: Rating,
+    /// The rating of the latest response.
+    pub latest: Rating,
+    /// How many points the rating fell.
+    pub drop: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn day(n: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(n * 86_400)
+    }
+
+    #[test]
+    fn test_history_is_ordered_by_time() {
+        let mut panel = Panel::new();
+        panel.add_response(1, 5, day(20)).unwrap();
+        panel.add_response(1, 9, day(10)).unwrap();
+        panel.add_response(1, 7, day(20)).unwrap();
+        panel.add_response(2, 10, day(5)).unwrap();
+
+        let scores: Vec<u8> = panel.history(&1).iter().map(|r| **r.score()).collect();
+        assert_eq!(scores, vec![9, 5, 7]);
+        assert!(panel.history(&3).is_empty());
+        assert_eq!(panel.len(), 2);
+        let latest: Vec<_> = panel.latest_scores().map(|(id, r)| (*id, *r)).collect();
+        assert_eq!(latest, vec![(1, 7), (2, 10)]);
+
+        assert_eq!(
+            panel.add_survey_response(SurveyResponse::new(3, 8).unwrap()),
+            Err(NetPromoterScoreError::MissingTimestamp)
+        );
+        assert_eq!(
+            panel.add_response(3, 11, day(1)),
+            Err(NetPromoterScoreError::InvalidRating(11))
+        );
+        assert_eq!(panel.len(), 2);
+    }
+
+    #[test]
+    fn test_trend_and_drops() {
+        let mut panel = Panel::new();
+        for (n, score) in [(0, 10), (10, 8), (20, 6)] {
+            panel.add_response("a", score, day(n)).unwrap();
+        }
+        panel.add_response("b", 9, day(0)).unwrap();
+        panel.add_response("b", 2, day(0)).unwrap();
+        panel.add_response("c", 3, day(0)).unwrap();
+        panel.add_response("c", 9, day(5)).unwrap();
+
+        let trend = panel.trend(&"a").unwrap();
+        assert_eq!((trend.responses, trend.change), (3, -4));
+        assert!((trend.slope_per_day + 0.2).abs() < 1e-12);
+        assert_eq!(panel.trend(&"b").unwrap().slope_per_day, 0.0);
+        assert!(panel.trend(&"z").is_none());
+
+        let drops = panel.score_drops(2);
+        let ids: Vec<_> = drops.iter().map(|d| (d.respondent_id, d.drop)).collect();
+        assert_eq!(ids, vec![("b", 7), ("a", 2)]);
+        assert!(panel.score_drops(8).is_empty());
+        assert_eq!(panel.score_drops(0).len(), 2);
+    }
+
+    #[test]
+    fn test_score_as_of() {
+        let mut panel = Panel::new();
+        panel.add_response(1, 10, day(1)).unwrap();
+        panel.add_response(1, 0, day(10)).unwrap();
+        panel.add_response(2, 8, day(5)).unwrap();
+
+        assert_eq!(panel.segment_counts_as_of(day(1)).total(), 0);
+        assert_eq!(panel.score_as_of(day(2)), 100);
+        assert_eq!(panel.score_as_of(day(6)), 50);
+        assert_eq!(panel.score_as_of(day(10)), 50);
+        assert_eq!(panel.score_as_of(day(11)), -50);
+
+        let survey = panel.survey_as_of(day(6));
+        assert_eq!(survey.len(), 2);
+        assert_eq!(**survey.get(&1).unwrap().score(), 10);
+        assert_eq!(panel.latest_survey().score(), -50);
+    }
+}
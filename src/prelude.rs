@@ -1,3 +1,18 @@
+pub use crate::aggregate::NpsAggregate;
+pub use crate::concurrent::ConcurrentSurvey;
+pub use crate::enps::{EmployeeSurvey, Reported, RespondentIds};
+pub use crate::histogram::ScoreHistogram;
+pub use crate::ingest::{IngestEntry, IngestReport, IngestWarning};
+pub use crate::metrics::{BottomBox, Ces, Csat, Mean, Median, Metric, Nps, TopBox};
+pub use crate::migration::MigrationMatrix;
+pub use crate::panel::{Panel, ScoreDrop, Trend};
+pub use crate::scale::{ClassificationScheme, NpsScheme, Scale};
+pub use crate::sentiment::{Mismatch, Polarity, Sentiment, SentimentAnalyzer};
+pub use crate::stats::{Comparison, ConfidenceInterval, IntervalMethod};
+pub use crate::summary::{NpsSummary, SummaryDelta};
+pub use crate::text::{TermContrast, TermCounts};
+pub use crate::timeseries::{Period, RollingTimeSeries, TimeBucket, TimeSeries};
 pub use crate::{
-    Classification, NetPromoterScoreError, NpsRating, Rating, ScoreCount, Survey, SurveyResponse,
+    BatchError, Classification, DuplicatePolicy, NetPromoterScoreError, NpsRating, Rating,
+    RejectedEntry, RoundingPolicy, ScoreCount, SegmentCounts, Survey, SurveyResponse,
 };
@@ -0,0 +1,286 @@
+//! Rating scales and classification thresholds.
+//!
+//! A [`Survey`](crate::Survey) validates and classifies ratings with a [`ClassificationScheme`].
+//! The default, [`NpsScheme`], is the standard 0 to 10 scale with detractors at 0 to 6, passives
+//! at 7 and 8 and promoters at 9 and 10. Legacy surveys on other scales, or business units with
+//! their own cut-offs, can use a [`Scale`] or their own implementation of the trait through
+//! [`Survey::with_scheme`](crate::Survey::with_scheme).
+//!
+//! Ratings are stored as a [`Rating`], so every scheme must lie within 0 to 10.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//!
+//! // A legacy 0 to 5 scale: 0 to 3 are detractors, 4 passives and 5 promoters
+//! let mut survey = Survey::with_scheme(Scale::new(0, 5, 4, 5).unwrap());
+//! survey.add_multiple_responses(vec![(1, 5), (2, 5), (3, 4), (4, 2)]).unwrap();
+//! assert_eq!(survey.score(), 25);
+//!
+//! assert_eq!(
+//!     survey.add_response(5, 6),
+//!     Err(NetPromoterScoreError::RatingOutOfRange { rating: 6, min: 0, max: 5 })
+//! );
+//! ```
+
+use std::convert::TryFrom;
+
+use crate::{Classification, NetPromoterScoreError, NpsRating, Rating};
+
+/// Defines the bounds of a rating scale and how ratings on it are classified.
+///
+/// Implementations must keep their bounds within 0 to 10 and classify every rating between them.
+pub trait ClassificationScheme {
+    /// Returns the lowest rating on the scale.
+    fn min_rating(&self) -> NpsRating;
+
+    /// Returns the highest rating on the scale, which must not exceed 10.
+    fn max_rating(&self) -> NpsRating;
+
+    /// Classifies a rating that lies within the scale's bounds.
+    fn classify(&self, rating: Rating) -> Classification;
+
+    /// Validates a rating against the scale's bounds.
+    ///
+    /// # Errors
+    ///
+    /// The default implementation returns `NetPromoterScoreError::RatingOutOfRange`, holding the
+    /// scale's bounds, if `value` lies outside them.
+    fn rating(&self, value: NpsRating) -> Result<Rating, NetPromoterScoreError> {
+        let (min, max) = (self.min_rating(), self.max_rating());
+        if (min..=max).contains(&value) {
+            Rating::try_from(value)
+        } else {
+            Err(NetPromoterScoreError::RatingOutOfRange {
+                rating: value,
+                min,
+                max,
+            })
+        }
+    }
+}
+
+/// The standard Net Promoter Score scheme: ratings from 0 to 10, with detractors at 0 to 6,
+/// passives at 7 and 8 and promoters at 9 and 10.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NpsScheme;
+
+impl ClassificationScheme for NpsScheme {
+    fn min_rating(&self) -> NpsRating {
+        0
+    }
+
+    fn max_rating(&self) -> NpsRating {
+        10
+    }
+
+    fn classify(&self, rating: Rating) -> Classification {
+        Classification::from(rating)
+    }
+
+    /// Validates a rating with `Rating::try_from`, returning
+    /// `NetPromoterScoreError::InvalidRating` for ratings above 10.
+    fn rating(&self, value: NpsRating) -> Result<Rating, NetPromoterScoreError> {
+        Rating::try_from(value)
+    }
+}
+
+/// A rating scale with custom bounds and classification thresholds.
+///
+/// Ratings from the minimum up to the passive threshold are detractors, ratings from the passive
+/// threshold up to the promoter threshold are passives, and ratings from the promoter threshold
+/// up to the maximum are promoters.
+///
+/// # Example
+///
+/// ```
+/// use net_promoter_score::prelude::*;
+///
+/// // A 1 to 10 scale where only 10 counts as a promoter
+/// let strict = Scale::new(1, 10, 7, 10).unwrap();
+/// assert_eq!(strict.classify(Rating::try_from(9).unwrap()), Classification::Passive);
+/// assert_eq!(strict.rating(0).unwrap_err().to_string(), "Rating 0 is outside the scale 1 to 10");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "ScaleData")
+)]
+pub struct Scale {
+    min: NpsRating,
+    max: NpsRating,
+    passive: NpsRating,
+    promoter: NpsRating,
+}
+
+impl Scale {
+    /// The standard Net Promoter Score scale, classified like [`NpsScheme`].
+    pub const NPS: Scale = Scale {
+        min: 0,
+        max: 10,
+        passive: 7,
+        promoter: 9,
+    };
+
+    /// Creates a scale from `min` to `max` whose passives start at `passive` and whose promoters
+    /// start at `promoter`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetPromoterScoreError::InvalidScale` unless
+    /// `min <= passive <= promoter <= max <= 10`.
+    pub fn new(
+        min: NpsRating,
+        max: NpsRating,
+        passive: NpsRating,
+        promoter: NpsRating,
+    ) -> Result<Self, NetPromoterScoreError> {
+        if min <= passive && passive <= promoter && promoter <= max && max <= 10 {
+            Ok(Self {
+                min,
+                max,
+                passive,
+                promoter,
+            })
+        } else {
+            Err(NetPromoterScoreError::InvalidScale)
+        }
+    }
+
+    /// Returns the lowest rating classified as passive.
+    pub fn passive_threshold(&self) -> NpsRating {
+        self.passive
+    }
+
+    /// Returns the lowest rating classified as a promoter.
+    pub fn promoter_threshold(&self) -> NpsRating {
+        self.promoter
+    }
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Scale::NPS
+    }
+}
+
+impl ClassificationScheme for Scale {
+    fn min_rating(&self) -> NpsRating {
+        self.min
+    }
+
+    fn max_rating(&self) -> NpsRating {
+        self.max
+    }
+
+    fn classify(&self, rating: Rating) -> Classification {
+        match *rating {
+            rating if rating >= self.promoter => Classification::Promoter,
+            rating if rating >= self.passive => Classification::Passive,
+            _ => Classification::Detractor,
+        }
+    }
+}
+
+impl<S: ClassificationScheme + ?Sized> ClassificationScheme for &S {
+    fn min_rating(&self) -> NpsRating {
+        (**self).min_rating()
+    }
+
+    fn max_rating(&self) -> NpsRating {
+        (**self).max_rating()
+    }
+
+    fn classify(&self, rating: Rating) -> Classification {
+        (**self).classify(rating)
+    }
+
+    fn rating(&self, value: NpsRating) -> Result<Rating, NetPromoterScoreError> {
+        (**self).rating(value)
+    }
+}
+
+// Deserializes a `Scale` through `Scale::new`, so invalid thresholds are rejected.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ScaleData {
+    min: NpsRating,
+    max: NpsRating,
+    passive: NpsRating,
+    promoter: NpsRating,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ScaleData> for Scale {
+    type Error = NetPromoterScoreError;
+
+    fn try_from(data: ScaleData) -> Result<Self, Self::Error> {
+        Scale::new(data.min, data.max, data.passive, data.promoter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_matches_nps_scheme() {
+        for value in 0..=10 {
+            let rating = Rating::try_from(value).unwrap();
+            assert_eq!(Scale::NPS.classify(rating), NpsScheme.classify(rating));
+            assert_eq!(Scale::NPS.rating(value), NpsScheme.rating(value));
+        }
+        assert_eq!(
+            NpsScheme.rating(11),
+            Err(NetPromoterScoreError::InvalidRating(11))
+        );
+        assert_eq!(
+            Scale::NPS.rating(11),
+            Err(NetPromoterScoreError::RatingOutOfRange {
+                rating: 11,
+                min: 0,
+                max: 10
+            })
+        );
+    }
+
+    #[test]
+    fn test_scale_thresholds() {
+        let scale = Scale::new(1, 10, 6, 9).unwrap();
+        let classify = |value| scale.classify(Rating::try_from(value).unwrap());
+        assert_eq!(classify(1), Classification::Detractor);
+        assert_eq!(classify(5), Classification::Detractor);
+        assert_eq!(classify(6), Classification::Passive);
+        assert_eq!(classify(9), Classification::Promoter);
+        assert_eq!(
+            scale.rating(0),
+            Err(NetPromoterScoreError::RatingOutOfRange {
+                rating: 0,
+                min: 1,
+                max: 10
+            })
+        );
+
+        // No passives at all
+        let binary = Scale::new(0, 5, 4, 4).unwrap();
+        assert_eq!(
+            binary.classify(Rating::try_from(4).unwrap()),
+            Classification::Promoter
+        );
+    }
+
+    #[test]
+    fn test_invalid_scales() {
+        for (min, max, passive, promoter) in
+            [(0, 11, 7, 9), (5, 4, 4, 4), (0, 10, 9, 7), (2, 10, 1, 9)]
+        {
+            assert_eq!(
+                Scale::new(min, max, passive, promoter),
+                Err(NetPromoterScoreError::InvalidScale)
+            );
+        }
+    }
+}
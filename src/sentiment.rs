@@ -0,0 +1,391 @@
+//! Lexicon-based sentiment scoring of response comments.
+//!
+//! A [`SentimentAnalyzer`] looks up every word of a comment in a lexicon of weighted sentiment
+//! words, flips and dampens the weight of words that follow a negation such as `not` or `never`
+//! in the same clause, and squashes the sum into a score between -1 and 1. The built-in English
+//! lexicon is tuned for customer feedback and can be extended or replaced. Nothing leaves the
+//! process.
+//!
+//! [`Survey::mismatches`](crate::Survey::mismatches) flags responses whose comment contradicts
+//! their score, such as a 9 with an angry comment or a 3 with a glowing one. These often mean the
+//! respondent misread the scale, so they can be reviewed or excluded.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//!
+//! let mut survey = Survey::new();
+//! survey.add_response_with_comment(1, 9, "Terrible support, the app keeps crashing").unwrap();
+//! survey.add_response_with_comment(2, 3, "Love it, excellent service!").unwrap();
+//! survey.add_response_with_comment(3, 2, "Not good, far too slow").unwrap();
+//! survey.add_response_with_comment(4, 10, "Not bad at all, really helpful").unwrap();
+//!
+//! let mismatches = survey.mismatches();
+//! let ids: Vec<_> = mismatches.iter().map(|m| m.respondent_id).collect();
+//! assert_eq!(ids, vec![1, 2]);
+//! assert_eq!(mismatches[0].sentiment.polarity, Polarity::Negative);
+//!
+//! survey.erase_respondents(mismatches.iter().map(|m| &m.respondent_id));
+//! assert_eq!(survey.score(), 0);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+use crate::scale::ClassificationScheme;
+use crate::text::words;
+use crate::{Classification, Rating, Survey};
+
+/// The built-in lexicon of sentiment words and their weights, from -3 (very negative) to 3 (very
+/// positive), in sorted order.
+pub const LEXICON: &[(&str, f64)] = &[
+    ("amazing", 3.0),
+    ("angry", -3.0),
+    ("annoying", -2.0),
+    ("awesome", 3.0),
+    ("awful", -3.0),
+    ("bad", -2.0),
+    ("best", 3.0),
+    ("brilliant", 3.0),
+    ("broken", -2.0),
+    ("buggy", -2.0),
+    ("bugs", -2.0),
+    ("complicated", -2.0),
+    ("confusing", -2.0),
+    ("crash", -2.0),
+    ("crashes", -2.0),
+    ("crashing", -2.0),
+    ("difficult", -2.0),
+    ("disappointed", -2.0),
+    ("disappointing", -2.0),
+    ("easy", 2.0),
+    ("efficient", 2.0),
+    ("enjoy", 2.0),
+    ("error", -1.0),
+    ("errors", -1.0),
+    ("excellent", 3.0),
+    ("expensive", -1.0),
+    ("fail", -2.0),
+    ("failed", -2.0),
+    ("fails", -2.0),
+    ("fantastic", 3.0),
+    ("fast", 2.0),
+    ("fine", 1.0),
+    ("friendly", 2.0),
+    ("frustrated", -2.0),
+    ("frustrating", -2.0),
+    ("good", 2.0),
+    ("great", 3.0),
+    ("happy", 2.0),
+    ("hard", -1.0),
+    ("hate", -3.0),
+    ("helpful", 2.0),
+    ("horrible", -3.0),
+    ("ignored", -2.0),
+    ("impressed", 2.0),
+    ("intuitive", 2.0),
+    ("laggy", -2.0),
+    ("love", 3.0),
+    ("loved", 3.0),
+    ("mess", -2.0),
+    ("nice", 2.0),
+    ("nightmare", -3.0),
+    ("outstanding", 3.0),
+    ("overpriced", -2.0),
+    ("perfect", 3.0),
+    ("pleased", 2.0),
+    ("poor", -2.0),
+    ("problem", -1.0),
+    ("problems", -1.0),
+    ("quick", 2.0),
+    ("recommend", 2.0),
+    ("reliable", 2.0),
+    ("responsive", 2.0),
+    ("rude", -2.0),
+    ("satisfied", 2.0),
+    ("scam", -3.0),
+    ("simple", 1.0),
+    ("slow", -2.0),
+    ("smooth", 2.0),
+    ("superb", 3.0),
+    ("terrible", -3.0),
+    ("thanks", 2.0),
+    ("unacceptable", -3.0),
+    ("unhelpful", -2.0),
+    ("unreliable", -2.0),
+    ("upset", -2.0),
+    ("useful", 2.0),
+    ("useless", -3.0),
+    ("waste", -2.0),
+    ("wasted", -2.0),
+    ("wonderful", 3.0),
+    ("worse", -2.0),
+    ("worst", -3.0),
+];
+
+// Words that reverse the sentiment of the words after them in the same clause.
+const NEGATIONS: &[&str] = &[
+    "aren't", "can't", "cannot", "couldn't", "didn't", "doesn't", "don't", "hardly", "isn't",
+    "never", "no", "not", "nothing", "wasn't", "without", "won't", "wouldn't",
+];
+
+// How many words after a negation are affected by it, and how their weight is scaled.
+const NEGATION_SCOPE: usize = 3;
+const NEGATION_FACTOR: f64 = -0.75;
+
+// Controls how quickly the summed weights approach ±1 when they are normalized.
+const NORMALIZATION: f64 = 15.0;
+
+const DEFAULT_THRESHOLD: f64 = 0.3;
+
+/// Whether a text reads as negative, neutral or positive overall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Polarity {
+    /// The score is below the negated threshold.
+    Negative,
+    /// The score is within the threshold of zero.
+    Neutral,
+    /// The score is above the threshold.
+    Positive,
+}
+
+// Implementing Display for Polarity to allow printing its name.
+impl Display for Polarity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// The sentiment of a text, as returned by [`SentimentAnalyzer::analyze`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sentiment {
+    /// The normalized score, from -1 (very negative) to 1 (very positive). Texts without any
+    /// sentiment words score 0.
+    pub score: f64,
+    /// The number of words that counted positively, after negation.
+    pub positive: usize,
+    /// The number of words that counted negatively, after negation.
+    pub negative: usize,
+    /// The overall polarity, decided by comparing the score with the analyzer's threshold.
+    pub polarity: Polarity,
+}
+
+/// Scores the sentiment of texts with a lexicon of weighted words.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentimentAnalyzer {
+    lexicon: BTreeMap<String, f64>,
+    threshold: f64,
+}
+
+impl Default for SentimentAnalyzer {
+    /// Creates an analyzer with the built-in [`LEXICON`].
+    fn default() -> Self {
+        Self::new(LEXICON.iter().copied())
+    }
+}
+
+impl SentimentAnalyzer {
+    /// Creates an analyzer with a custom lexicon of words and their weights. Words are matched
+    /// ignoring case.
+    pub fn new<W: Into<String>>(lexicon: impl IntoIterator<Item = (W, f64)>) -> Self {
+        Self {
+            lexicon: lexicon
+                .into_iter()
+                .map(|(word, weight)| (word.into().to_lowercase(), weight))
+                .collect(),
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+
+    /// Returns the analyzer with `word` added to its lexicon, or its weight replaced.
+    pub fn with_word(mut self, word: impl Into<String>, weight: f64) -> Self {
+        self.lexicon.insert(word.into().to_lowercase(), weight);
+        self
+    }
+
+    /// Returns the analyzer with a new polarity threshold. Scores above `threshold` are positive
+    /// and scores below `-threshold` are negative. The default is 0.3.
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Returns the weight of `word` in the lexicon, if it has one.
+    pub fn weight(&self, word: &str) -> Option<f64> {
+        self.lexicon.get(&word.to_lowercase()).copied()
+    }
+
+    /// Scores the sentiment of `text`.
+    ///
+    /// Up to three words following a negation in the same clause have their weight reversed
+    /// and dampened, so `not good` reads as mildly negative and `not bad` as mildly positive.
+    /// Clauses end at punctuation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::sentiment::{Polarity, SentimentAnalyzer};
+    ///
+    /// let analyzer = SentimentAnalyzer::default();
+    /// assert_eq!(analyzer.analyze("Great product!").polarity, Polarity::Positive);
+    /// assert_eq!(analyzer.analyze("It's not great").polarity, Polarity::Negative);
+    /// assert_eq!(analyzer.analyze("Arrived on Tuesday").polarity, Polarity::Neutral);
+    /// ```
+    pub fn analyze(&self, text: &str) -> Sentiment {
+        let mut total = 0.0;
+        let mut positive = 0;
+        let mut negative = 0;
+        for clause in text.split(['.', ',', ';', ':', '!', '?']) {
+            let mut negated = 0;
+            for word in words(clause) {
+                if NEGATIONS.contains(&word.as_str()) {
+                    negated = NEGATION_SCOPE;
+                    continue;
+                }
+                if let Some(mut weight) = self.lexicon.get(&word).copied() {
+                    if negated > 0 {
+                        weight *= NEGATION_FACTOR;
+                    }
+                    if weight > 0.0 {
+                        positive += 1;
+                    } else if weight < 0.0 {
+                        negative += 1;
+                    }
+                    total += weight;
+                }
+                negated = negated.saturating_sub(1);
+            }
+        }
+
+        let score = total / (total * total + NORMALIZATION).sqrt();
+        let polarity = if score > self.threshold {
+            Polarity::Positive
+        } else if score < -self.threshold {
+            Polarity::Negative
+        } else {
+            Polarity::Neutral
+        };
+        Sentiment {
+            score,
+            positive,
+            negative,
+            polarity,
+        }
+    }
+}
+
+/// A response whose comment contradicts its score, as returned by
+/// [`Survey::mismatches`](crate::Survey::mismatches).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mismatch<T> {
+    /// The respondent ID of the response.
+    pub respondent_id: T,
+    /// The rating given.
+    pub score: Rating,
+    /// The classification of the rating.
+    pub classification: Classification,
+    /// The sentiment of the comment.
+    pub sentiment: Sentiment,
+}
+
+impl<T: PartialEq + Clone, S: ClassificationScheme> Survey<T, S> {
+    /// Returns the responses whose comment contradicts their classification under the built-in
+    /// sentiment lexicon: promoters with a negative comment and detractors with a positive one.
+    ///
+    /// Responses without a comment and passives are never flagged. Use
+    /// [`mismatches_with`](Survey::mismatches_with) for a custom analyzer.
+    pub fn mismatches(&self) -> Vec<Mismatch<T>> {
+        self.mismatches_with(&SentimentAnalyzer::default())
+    }
+
+    /// Returns the responses whose comment contradicts their classification under `analyzer`,
+    /// in respondent ID order.
+    pub fn mismatches_with(&self, analyzer: &SentimentAnalyzer) -> Vec<Mismatch<T>> {
+        let mut mismatches = Vec::new();
+        for response in self.responses.values() {
+            let Some(comment) = response.comment() else {
+                continue;
+            };
+            let classification = self.scheme.classify(*response.score());
+            let sentiment = analyzer.analyze(comment);
+            let contradicts = matches!(
+                (classification, sentiment.polarity),
+                (Classification::Promoter, Polarity::Negative)
+                    | (Classification::Detractor, Polarity::Positive)
+            );
+            if contradicts {
+                mismatches.push(Mismatch {
+                    respondent_id: response.respondent_id().clone(),
+                    score: *response.score(),
+                    classification,
+                    sentiment,
+                });
+            }
+        }
+        mismatches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lexicon_is_sorted() {
+        assert!(LEXICON.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(NEGATIONS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_negation() {
+        let analyzer = SentimentAnalyzer::default();
+        let good = analyzer.analyze("good");
+        assert_eq!((good.positive, good.negative), (1, 0));
+        assert!((good.score - 2.0 / 19f64.sqrt()).abs() < 1e-12);
+
+        let not_good = analyzer.analyze("This is not very good");
+        assert_eq!((not_good.positive, not_good.negative), (0, 1));
+        assert!((not_good.score + 1.5 / 17.25f64.sqrt()).abs() < 1e-12);
+        assert_eq!(not_good.polarity, Polarity::Negative);
+
+        // Negation stops at the end of the clause and after three words.
+        assert_eq!(analyzer.analyze("No issues, great").positive, 1);
+        assert_eq!(
+            analyzer
+                .analyze("Never thought it would be this good")
+                .positive,
+            1
+        );
+        assert_eq!(
+            analyzer.analyze("Don\u{2019}t hate it").polarity,
+            Polarity::Positive
+        );
+    }
+
+    #[test]
+    fn test_custom_analyzer() {
+        let analyzer = SentimentAnalyzer::new([("Meh", -1.0)]).with_word("SOLID", 2.0);
+        assert_eq!(analyzer.weight("meh"), Some(-1.0));
+        assert_eq!(analyzer.weight("great"), None);
+        assert_eq!(analyzer.analyze("great").polarity, Polarity::Neutral);
+        assert_eq!(analyzer.analyze("meh").polarity, Polarity::Neutral);
+        assert_eq!(
+            analyzer.with_threshold(0.2).analyze("meh").polarity,
+            Polarity::Negative
+        );
+
+        let mut survey = Survey::new();
+        survey.add_response_with_comment(1, 0, "Solid").unwrap();
+        survey.add_response_with_comment(2, 8, "Meh").unwrap();
+        survey.add_response(3, 10).unwrap();
+        let analyzer = SentimentAnalyzer::new([("solid", 2.0)]);
+        let mismatches = survey.mismatches_with(&analyzer);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].classification, Classification::Detractor);
+        assert_eq!(*mismatches[0].score, 0);
+    }
+}
@@ -0,0 +1,273 @@
+//! `Serialize` and `Deserialize` for [`Survey`], behind the `serde` feature.
+//!
+//! A survey is written as its current responses, the earlier responses kept under
+//! [`DuplicatePolicy::KeepAll`], its rounding and duplicate policies and its classification
+//! scheme. The rating histogram and the cached score are derived from the responses, so they are
+//! rebuilt when a survey is read back rather than trusted from the input.
+
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::scale::ClassificationScheme;
+use crate::{DuplicatePolicy, RoundingPolicy, Survey, SurveyResponse};
+
+impl<T: Serialize, C: Serialize> Serialize for Survey<T, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut survey = serializer.serialize_struct("Survey", 5)?;
+        survey.serialize_field("responses", &Sequence(self.responses.values()))?;
+        survey.serialize_field("history", &Sequence(self.history.values().flatten()))?;
+        survey.serialize_field("rounding_policy", &self.rounding_policy)?;
+        survey.serialize_field("duplicate_policy", &self.duplicate_policy)?;
+        survey.serialize_field("scheme", &self.scheme)?;
+        survey.end()
+    }
+}
+
+// Serializes the items of an iterator as a sequence without collecting them first.
+struct Sequence<I>(I);
+
+impl<I> Serialize for Sequence<I>
+where
+    I: Iterator + Clone,
+    I::Item: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.clone())
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(
+    rename = "Survey",
+    bound(deserialize = "T: Deserialize<'de>, C: Deserialize<'de> + Default")
+)]
+struct SurveyData<T, C> {
+    responses: Vec<SurveyResponse<T>>,
+    #[serde(default)]
+    history: Vec<SurveyResponse<T>>,
+    #[serde(default)]
+    rounding_policy: RoundingPolicy,
+    #[serde(default)]
+    duplicate_policy: DuplicatePolicy,
+    #[serde(default)]
+    scheme: C,
+}
+
+impl<'de, T, C> Deserialize<'de> for Survey<T, C>
+where
+    T: Deserialize<'de> + Ord + Clone,
+    C: Deserialize<'de> + Default + ClassificationScheme,
+{
+    /// Reads a survey and rebuilds its rating histogram. The score is recomputed on first use.
+    ///
+    /// Fails if two current responses share a respondent ID, if an earlier response belongs to a
+    /// respondent without a current one, or if the rating of a current or earlier response lies
+    /// outside the scheme's scale. Ratings are validated as they are read.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = SurveyData::<T, C>::deserialize(deserializer)?;
+
+        let mut survey = Survey::with_scheme(data.scheme);
+        survey.set_duplicate_policy(DuplicatePolicy::Reject);
+        for response in data.responses {
+            survey
+                .insert_response(response.respondent_id.clone(), response)
+                .map_err(D::Error::custom)?;
+        }
+        for response in data.history {
+            if !survey.responses.contains_key(&response.respondent_id) {
+                return Err(D::Error::custom(
+                    "history holds a response for a respondent without a current response",
+                ));
+            }
+            survey
+                .scheme
+                .rating(*response.score)
+                .map_err(D::Error::custom)?;
+            survey
+                .history
+                .entry(response.respondent_id.clone())
+                .or_default()
+                .push(response);
+        }
+
+        survey.set_rounding_policy(data.rounding_policy);
+        survey.set_duplicate_policy(data.duplicate_policy);
+        Ok(survey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_survey_round_trip() {
+        let mut survey = Survey::new();
+        survey.set_duplicate_policy(DuplicatePolicy::KeepAll);
+        survey.set_rounding_policy(RoundingPolicy::Truncate);
+        survey
+            .add_response_at(1, 10, UNIX_EPOCH + Duration::from_secs(60))
+            .unwrap();
+        survey
+            .add_response_with_attributes(2, 3, [("region", "EMEA")])
+            .unwrap();
+        survey.add_response(3, 8).unwrap();
+        survey.add_response(2, 9).unwrap();
+
+        let json = serde_json::to_string(&survey).unwrap();
+        let copy: Survey<u32> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(
+            copy.responses().collect::<Vec<_>>(),
+            survey.responses().collect::<Vec<_>>()
+        );
+        assert_eq!(copy.history(&2), survey.history(&2));
+        assert_eq!(copy.histogram(), survey.histogram());
+        assert_eq!(copy.duplicate_policy(), DuplicatePolicy::KeepAll);
+        assert_eq!(copy.rounding_policy(), RoundingPolicy::Truncate);
+        assert_eq!(copy.score_exact(), survey.score_exact());
+        assert_eq!(serde_json::to_string(&copy).unwrap(), json);
+    }
+
+    #[test]
+    fn test_survey_from_minimal_json() {
+        let json = r#"{"responses": [
+            {"respondent_id": "a", "score": 10},
+            {"respondent_id": "b", "score": 0, "attributes": {"plan": "pro"}}
+        ]}"#;
+        let survey: Survey<String> = serde_json::from_str(json).unwrap();
+        assert_eq!(survey.score(), 0);
+        assert_eq!(survey.histogram().total(), 2);
+        assert_eq!(survey.group_by("plan")["pro"].detractors, 1);
+        assert_eq!(survey.duplicate_policy(), DuplicatePolicy::KeepLast);
+    }
+
+    #[test]
+    fn test_invalid_surveys_are_rejected() {
+        let out_of_range = r#"{"responses": [{"respondent_id": 1, "score": 11}]}"#;
+        let error = serde_json::from_str::<Survey<u32>>(out_of_range)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("Invalid rating value: 11"));
+
+        let duplicate = r#"{"responses": [
+            {"respondent_id": 1, "score": 1},
+            {"respondent_id": 1, "score": 2}
+        ]}"#;
+        let error = serde_json::from_str::<Survey<u32>>(duplicate)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("Duplicate respondent"));
+
+        let orphan = r#"{"responses": [], "history": [{"respondent_id": 1, "score": 2}]}"#;
+        assert!(serde_json::from_str::<Survey<u32>>(orphan).is_err());
+    }
+
+    #[test]
+    fn test_survey_with_scale() {
+        let mut survey = Survey::with_scheme(Scale::new(1, 5, 4, 5).unwrap());
+        survey.add_multiple_responses(vec![(1, 5), (2, 3)]).unwrap();
+
+        let json = serde_json::to_string(&survey).unwrap();
+        assert!(json.ends_with(r#""scheme":{"min":1,"max":5,"passive":4,"promoter":5}}"#));
+        let copy: Survey<u32, Scale> = serde_json::from_str(&json).unwrap();
+        assert_eq!(copy.scheme(), survey.scheme());
+        assert_eq!(copy.score(), 0);
+
+        let out_of_scale = json.replace(r#""score":3"#, r#""score":0"#);
+        assert!(serde_json::from_str::<Survey<u32, Scale>>(&out_of_scale).is_err());
+        let out_of_scale_history = r#"{"responses": [{"respondent_id": 1, "score": 5}],
+            "history": [{"respondent_id": 1, "score": 0}],
+            "scheme": {"min": 1, "max": 5, "passive": 4, "promoter": 5}}"#;
+        let error = serde_json::from_str::<Survey<u32, Scale>>(out_of_scale_history)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("Rating 0 is outside the scale"));
+        let bad_scale = json.replace(r#""passive":4"#, r#""passive":6"#);
+        assert!(serde_json::from_str::<Survey<u32, Scale>>(&bad_scale).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_round_trip() {
+        let mut survey = Survey::new();
+        survey
+            .add_response_at(1, 10, UNIX_EPOCH + Duration::from_secs(60))
+            .unwrap();
+        survey
+            .add_response_with_attributes(2, 3, [("region", "EMEA")])
+            .unwrap();
+
+        let aggregate = survey.aggregate(&["region"]);
+        let json = serde_json::to_string(&aggregate).unwrap();
+        assert_eq!(
+            serde_json::from_str::<NpsAggregate>(&json).unwrap(),
+            aggregate
+        );
+
+        let minimal = r#"{"histogram": {"counts": [0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1]}}"#;
+        let aggregate: NpsAggregate = serde_json::from_str(minimal).unwrap();
+        assert_eq!(aggregate.score_exact(), 100.0);
+        assert_eq!(aggregate.time_bounds(), None);
+        let mut keyed = survey.aggregate(&["region"]);
+        keyed.merge(&aggregate);
+        assert_eq!(keyed.keys().count(), 0);
+
+        let json = serde_json::to_string(&NpsAggregate::new()).unwrap();
+        let mut empty: NpsAggregate = serde_json::from_str(&json).unwrap();
+        empty.merge(&survey.aggregate(&["region"]));
+        assert_eq!(empty.group("region").unwrap()["EMEA"].total(), 1);
+    }
+
+    #[test]
+    fn test_summary_field_names() {
+        let mut survey = Survey::new();
+        survey
+            .add_multiple_responses(vec![(1, 10), (2, 7)])
+            .unwrap();
+
+        let json = serde_json::to_value(survey.summary()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "total": 2,
+                "promoters": 1,
+                "passives": 1,
+                "detractors": 0,
+                "promoter_percentage": 50.0,
+                "passive_percentage": 50.0,
+                "detractor_percentage": 0.0,
+                "score_exact": 50.0,
+                "score": 50,
+                "distribution": [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1]
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<NpsSummary>(json).unwrap(),
+            survey.summary()
+        );
+    }
+
+    #[test]
+    fn test_rating_and_classification() {
+        let rating: Rating = serde_json::from_str("7").unwrap();
+        assert_eq!(*rating, 7);
+        assert_eq!(serde_json::to_string(&rating).unwrap(), "7");
+        assert!(serde_json::from_str::<Rating>("11").is_err());
+
+        let classification = Classification::from(rating);
+        let json = serde_json::to_string(&classification).unwrap();
+        assert_eq!(json, r#""Passive""#);
+        assert_eq!(
+            serde_json::from_str::<Classification>(&json).unwrap(),
+            classification
+        );
+
+        let error = NetPromoterScoreError::InvalidRating(12);
+        let json = serde_json::to_string(&error).unwrap();
+        assert_eq!(
+            serde_json::from_str::<NetPromoterScoreError>(&json).unwrap(),
+            error
+        );
+    }
+}
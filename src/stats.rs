@@ -0,0 +1,620 @@
+//! Statistical tools for judging how reliable a Net Promoter Score is.
+//!
+//! Every respondent contributes +1 (promoter), 0 (passive) or -1 (detractor) to the score, so the
+//! NPS is the mean of a trinomial variable scaled to the -100 to 100 range. The intervals in this
+//! module are computed from the promoter, passive and detractor counts in [`SegmentCounts`].
+//!
+//! [`compare`] and [`compare_paired`] test whether the difference between two surveys is
+//! statistically significant.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//!
+//! let mut survey = Survey::new();
+//! survey
+//!     .add_multiple_responses(vec![(1, 10), (2, 9), (3, 9), (4, 8), (5, 7), (6, 6)])
+//!     .unwrap();
+//!
+//! let interval = survey.confidence_interval(0.95).unwrap();
+//! println!(
+//!     "NPS {:.1} ± {:.1} ({:.1} to {:.1})",
+//!     interval.estimate, interval.margin_of_error, interval.lower, interval.upper
+//! );
+//! # assert!(interval.lower < interval.estimate && interval.estimate < interval.upper);
+//! ```
+
+use crate::scale::ClassificationScheme;
+use crate::{Classification, NetPromoterScoreError, SegmentCounts, Survey};
+
+/// The method used to estimate the standard error of a Net Promoter Score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntervalMethod {
+    /// Uses the sample variance of the +1/0/-1 respondent scores, `(p + d - (p - d)²) / (n - 1)`.
+    /// Requires at least two responses.
+    #[default]
+    Trinomial,
+    /// Uses the maximum-likelihood variance `(p + d - (p - d)²) / n`. Requires at least one response.
+    Wald,
+    /// Adds 0.75 pseudo-responses to the promoter and detractor counts and 1.5 to the passives
+    /// (three in total) before applying the Wald formula, which keeps small and lopsided samples
+    /// from producing zero-width intervals.
+    AdjustedWald,
+}
+
+/// A confidence interval around a Net Promoter Score, expressed in NPS points (-100 to 100).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceInterval {
+    /// The point estimate the interval is centred on. For [`IntervalMethod::AdjustedWald`] this is
+    /// the adjusted score rather than the observed one.
+    pub estimate: f64,
+    /// The lower bound of the interval, clamped to -100.
+    pub lower: f64,
+    /// The upper bound of the interval, clamped to 100.
+    pub upper: f64,
+    /// The standard error of the score.
+    pub standard_error: f64,
+    /// Half the width of the unclamped interval, `z * standard_error`.
+    pub margin_of_error: f64,
+    /// The confidence level the interval was computed for, e.g. `0.95`.
+    pub level: f64,
+    /// The method used to estimate the standard error.
+    pub method: IntervalMethod,
+}
+
+impl SegmentCounts {
+    /// Computes a confidence interval for the Net Promoter Score of these counts.
+    ///
+    /// `level` is the confidence level as a fraction strictly between 0 and 1, such as `0.95`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetPromoterScoreError::InvalidConfidenceLevel` if `level` is out of range and
+    /// `NetPromoterScoreError::InsufficientResponses` if there are too few responses for `method`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let counts = SegmentCounts { detractors: 30, passives: 30, promoters: 40 };
+    /// let interval = counts.confidence_interval(0.95, IntervalMethod::Wald).unwrap();
+    ///
+    /// assert_eq!(interval.estimate, 10.0);
+    /// assert!((interval.standard_error - 8.3066).abs() < 1e-4);
+    /// ```
+    pub fn confidence_interval(
+        &self,
+        level: f64,
+        method: IntervalMethod,
+    ) -> Result<ConfidenceInterval, NetPromoterScoreError> {
+        let z = z_for_level(level)?;
+        let total = self.total();
+        let (estimate, standard_error) = match method {
+            IntervalMethod::Trinomial => {
+                require_responses(total, 2)?;
+                let (p, d) = self.proportions();
+                let variance = trinomial_variance(p, d) / (total - 1) as f64;
+                (self.score_exact(), 100.0 * variance.sqrt())
+            }
+            IntervalMethod::Wald => {
+                require_responses(total, 1)?;
+                let (p, d) = self.proportions();
+                let variance = trinomial_variance(p, d) / total as f64;
+                (self.score_exact(), 100.0 * variance.sqrt())
+            }
+            IntervalMethod::AdjustedWald => {
+                let adjusted_total = total as f64 + 3.0;
+                let p = (self.promoters as f64 + 0.75) / adjusted_total;
+                let d = (self.detractors as f64 + 0.75) / adjusted_total;
+                let variance = trinomial_variance(p, d) / adjusted_total;
+                (100.0 * (p - d), 100.0 * variance.sqrt())
+            }
+        };
+
+        let margin_of_error = z * standard_error;
+        Ok(ConfidenceInterval {
+            estimate,
+            lower: (estimate - margin_of_error).max(-100.0),
+            upper: (estimate + margin_of_error).min(100.0),
+            standard_error,
+            margin_of_error,
+            level,
+            method,
+        })
+    }
+
+    /// Returns the promoter and detractor proportions. Callers must ensure the total is non-zero.
+    pub(crate) fn proportions(&self) -> (f64, f64) {
+        let total = self.total() as f64;
+        (
+            self.promoters as f64 / total,
+            self.detractors as f64 / total,
+        )
+    }
+}
+
+/// The result of a significance test on the difference between two Net Promoter Scores.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    /// The score of the second survey minus the score of the first, in NPS points.
+    pub difference: f64,
+    /// The standard error of the difference, in NPS points.
+    pub standard_error: f64,
+    /// The z-statistic, `difference / standard_error`.
+    pub z_statistic: f64,
+    /// The two-sided p-value of the z-statistic.
+    pub p_value: f64,
+    /// The significance level the test was run at, e.g. `0.05`.
+    pub alpha: f64,
+    /// Whether the difference is significant, i.e. `p_value < alpha`.
+    pub significant: bool,
+    /// The number of respondents present in both surveys. Always `0` for [`compare`].
+    pub paired_respondents: usize,
+}
+
+/// Tests whether the difference between the scores of two independent surveys is significant.
+///
+/// The surveys are treated as independent samples, so their respondent ID types may differ. The
+/// difference is `after - before` and its standard error combines the trinomial variances of both
+/// surveys.
+///
+/// # Errors
+///
+/// Returns `NetPromoterScoreError::InvalidSignificanceLevel` if `alpha` is not between 0 and 1, and
+/// `NetPromoterScoreError::InsufficientResponses` if either survey has fewer than two responses.
+///
+/// # Example
+///
+/// ```
+/// use net_promoter_score::prelude::*;
+/// use net_promoter_score::stats::compare;
+///
+/// let mut q1 = Survey::new();
+/// q1.add_bulk_responses_auto_id(&[(3, 30), (7, 30), (10, 40)]).unwrap();
+/// let mut q2: Survey<String> = Survey::new();
+/// let mut next_id = 0;
+/// let id_fn = || {
+///     next_id += 1;
+///     format!("customer_{}", next_id)
+/// };
+/// q2.add_bulk_responses(id_fn, &[(3, 20), (7, 30), (10, 50)]).unwrap();
+///
+/// let comparison = compare(&q1, &q2, 0.05).unwrap();
+/// assert!((comparison.difference - 20.0).abs() < 1e-9);
+/// // A 20 point move on 100 responses each is not significant at the 5% level
+/// assert!(!comparison.significant);
+/// ```
+pub fn compare<A, B, SA, SB>(
+    before: &Survey<A, SA>,
+    after: &Survey<B, SB>,
+    alpha: f64,
+) -> Result<Comparison, NetPromoterScoreError>
+where
+    A: PartialEq + Ord + Clone,
+    B: PartialEq + Ord + Clone,
+    SA: ClassificationScheme,
+    SB: ClassificationScheme,
+{
+    check_alpha(alpha)?;
+    let (before_counts, after_counts) = (before.segment_counts(), after.segment_counts());
+    let variance =
+        sample_variance_of_mean(&before_counts)? + sample_variance_of_mean(&after_counts)?;
+    Ok(significance(
+        after_counts.score_exact() - before_counts.score_exact(),
+        100.0 * variance.sqrt(),
+        alpha,
+        0,
+    ))
+}
+
+/// Tests whether the difference between the scores of two survey waves is significant, taking
+/// into account respondents who answered both.
+///
+/// Respondents are matched on their respondent ID. The covariance of the matched respondents'
+/// +1/0/-1 scores is subtracted from the variance of the difference, which yields the classic
+/// paired test when both waves contain exactly the same respondents and falls back to
+/// [`compare`] when they share fewer than two.
+///
+/// # Errors
+///
+/// See [`compare`].
+///
+/// # Example
+///
+/// ```
+/// use net_promoter_score::prelude::*;
+/// use net_promoter_score::stats::{compare, compare_paired};
+///
+/// let before = Survey::from_responses::<NetPromoterScoreError>(
+///     (1..=10).zip([10, 10, 8, 8, 8, 6, 6, 6, 6, 9]),
+/// )
+/// .unwrap();
+/// let after = Survey::from_responses::<NetPromoterScoreError>(
+///     (1..=10).zip([10, 10, 9, 9, 8, 8, 6, 6, 9, 9]),
+/// )
+/// .unwrap();
+///
+/// let paired = compare_paired(&before, &after, 0.05).unwrap();
+/// assert_eq!(paired.paired_respondents, 10);
+/// assert!(paired.significant);
+/// assert!(!compare(&before, &after, 0.05).unwrap().significant);
+/// ```
+pub fn compare_paired<T, SA, SB>(
+    before: &Survey<T, SA>,
+    after: &Survey<T, SB>,
+    alpha: f64,
+) -> Result<Comparison, NetPromoterScoreError>
+where
+    T: PartialEq + Ord + Clone,
+    SA: ClassificationScheme,
+    SB: ClassificationScheme,
+{
+    check_alpha(alpha)?;
+    let (before_counts, after_counts) = (before.segment_counts(), after.segment_counts());
+    let mut variance =
+        sample_variance_of_mean(&before_counts)? + sample_variance_of_mean(&after_counts)?;
+
+    let pairs: Vec<(f64, f64)> = before
+        .responses
+        .iter()
+        .filter_map(|(respondent_id, first)| {
+            after.responses.get(respondent_id).map(|second| {
+                (
+                    contribution(before.classify(first.score)),
+                    contribution(after.classify(second.score)),
+                )
+            })
+        })
+        .collect();
+
+    if pairs.len() >= 2 {
+        let paired = pairs.len() as f64;
+        let mean_before = pairs.iter().map(|(x, _)| x).sum::<f64>() / paired;
+        let mean_after = pairs.iter().map(|(_, y)| y).sum::<f64>() / paired;
+        let covariance = pairs
+            .iter()
+            .map(|(x, y)| (x - mean_before) * (y - mean_after))
+            .sum::<f64>()
+            / (paired - 1.0);
+        variance -= 2.0 * paired * covariance
+            / (before_counts.total() as f64 * after_counts.total() as f64);
+    }
+
+    Ok(significance(
+        after_counts.score_exact() - before_counts.score_exact(),
+        100.0 * variance.max(0.0).sqrt(),
+        alpha,
+        pairs.len(),
+    ))
+}
+
+fn significance(
+    difference: f64,
+    standard_error: f64,
+    alpha: f64,
+    paired_respondents: usize,
+) -> Comparison {
+    let z_statistic = if standard_error > 0.0 {
+        difference / standard_error
+    } else if difference == 0.0 {
+        0.0
+    } else {
+        difference.signum() * f64::INFINITY
+    };
+    let p_value = erfc(z_statistic.abs() / std::f64::consts::SQRT_2);
+    Comparison {
+        difference,
+        standard_error,
+        z_statistic,
+        p_value,
+        alpha,
+        significant: p_value < alpha,
+        paired_respondents,
+    }
+}
+
+fn check_alpha(alpha: f64) -> Result<(), NetPromoterScoreError> {
+    if alpha > 0.0 && alpha < 1.0 {
+        Ok(())
+    } else {
+        Err(NetPromoterScoreError::InvalidSignificanceLevel)
+    }
+}
+
+/// The squared standard error of a survey's mean +1/0/-1 score, using the sample variance.
+fn sample_variance_of_mean(counts: &SegmentCounts) -> Result<f64, NetPromoterScoreError> {
+    let total = counts.total();
+    require_responses(total, 2)?;
+    let (p, d) = counts.proportions();
+    Ok(trinomial_variance(p, d) / (total - 1) as f64)
+}
+
+/// A respondent's contribution to the score: +1 for promoters, 0 for passives, -1 for detractors.
+pub(crate) fn contribution(classification: Classification) -> f64 {
+    match classification {
+        Classification::Detractor => -1.0,
+        Classification::Passive => 0.0,
+        Classification::Promoter => 1.0,
+    }
+}
+
+/// The variance of a single respondent's +1/0/-1 score given promoter and detractor proportions.
+pub(crate) fn trinomial_variance(promoters: f64, detractors: f64) -> f64 {
+    promoters + detractors - (promoters - detractors).powi(2)
+}
+
+fn require_responses(total: usize, required: usize) -> Result<(), NetPromoterScoreError> {
+    if total < required {
+        Err(NetPromoterScoreError::InsufficientResponses(total))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the two-sided critical value of the standard normal distribution for `level`.
+pub(crate) fn z_for_level(level: f64) -> Result<f64, NetPromoterScoreError> {
+    if level > 0.0 && level < 1.0 {
+        Ok(inverse_normal_cdf(1.0 - (1.0 - level) / 2.0))
+    } else {
+        Err(NetPromoterScoreError::InvalidConfidenceLevel)
+    }
+}
+
+/// The complementary error function, with a fractional error below 1.2e-7 (Numerical Recipes'
+/// Chebyshev fit).
+pub(crate) fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let polynomial = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let result = t * polynomial.exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+/// Inverse of the standard normal cumulative distribution function, using Acklam's rational
+/// approximation (relative error below 1.15e-9). `p` must be in the open interval (0, 1).
+pub(crate) fn inverse_normal_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COUNTS: SegmentCounts = SegmentCounts {
+        detractors: 30,
+        passives: 30,
+        promoters: 40,
+    };
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    fn survey(ratings: &[(u8, usize)]) -> Survey<i32> {
+        let mut survey = Survey::new();
+        survey.add_bulk_responses_auto_id(ratings).unwrap();
+        survey
+    }
+
+    #[test]
+    fn test_erfc() {
+        assert!((erfc(0.0) - 1.0).abs() < 1e-7);
+        assert!((erfc(1.0) - 0.157_299_207_050_285).abs() < 1e-7);
+        assert!((erfc(-1.0) - 1.842_700_792_949_715).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_inverse_normal_cdf() {
+        assert_close(inverse_normal_cdf(0.975), 1.959_963_984_540_054);
+        assert_close(inverse_normal_cdf(0.95), 1.644_853_626_951_472);
+        assert_close(inverse_normal_cdf(0.995), 2.575_829_303_548_9);
+        assert_close(inverse_normal_cdf(0.5), 0.0);
+        assert!((inverse_normal_cdf(1e-10) - -6.361_340_902_404_056).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_wald_interval() {
+        let interval = COUNTS
+            .confidence_interval(0.95, IntervalMethod::Wald)
+            .unwrap();
+        assert_close(interval.estimate, 10.0);
+        assert_close(interval.standard_error, 8.306_623_862_918_075);
+        assert_close(
+            interval.margin_of_error,
+            1.959_963_984_540_054 * 8.306_623_862_918_075,
+        );
+        assert_close(interval.lower, 10.0 - interval.margin_of_error);
+        assert_close(interval.upper, 10.0 + interval.margin_of_error);
+    }
+
+    #[test]
+    fn test_trinomial_interval() {
+        let interval = COUNTS
+            .confidence_interval(0.95, IntervalMethod::Trinomial)
+            .unwrap();
+        assert_close(interval.estimate, 10.0);
+        assert_close(interval.standard_error, 8.348_471_099_367_218);
+    }
+
+    #[test]
+    fn test_adjusted_wald_interval() {
+        let interval = COUNTS
+            .confidence_interval(0.95, IntervalMethod::AdjustedWald)
+            .unwrap();
+        assert_close(interval.estimate, 9.708_737_864_077_671);
+        assert_close(interval.standard_error, 8.153_555_411_212_354);
+        assert_close(interval.lower, -6.271_937_087_850_21);
+        assert_close(interval.upper, 25.689_412_816_005_55);
+    }
+
+    #[test]
+    fn test_interval_is_clamped() {
+        let counts = SegmentCounts {
+            detractors: 0,
+            passives: 1,
+            promoters: 4,
+        };
+        let interval = counts
+            .confidence_interval(0.99, IntervalMethod::Wald)
+            .unwrap();
+        assert_eq!(interval.upper, 100.0);
+        assert!(interval.estimate + interval.margin_of_error > 100.0);
+    }
+
+    #[test]
+    fn test_compare_independent() {
+        let before = survey(&[(3, 30), (7, 30), (10, 40)]);
+        let after = survey(&[(3, 20), (7, 30), (10, 50)]);
+
+        let comparison = compare(&before, &after, 0.1).unwrap();
+        assert_close(comparison.difference, 20.0);
+        assert!((comparison.standard_error - 11.459_194_182_538_81).abs() < 1e-6);
+        assert!((comparison.z_statistic - 1.745_323_421_648_218).abs() < 1e-6);
+        assert!((comparison.p_value - 0.080_928_587_184_485).abs() < 1e-6);
+        assert!(comparison.significant);
+        assert!(!compare(&before, &after, 0.05).unwrap().significant);
+        assert_eq!(comparison.paired_respondents, 0);
+
+        let reversed = compare(&after, &before, 0.1).unwrap();
+        assert_close(reversed.difference, -20.0);
+        assert_close(reversed.p_value, comparison.p_value);
+    }
+
+    #[test]
+    fn test_compare_paired() {
+        let ratings_before = [10, 10, 8, 8, 8, 6, 6, 6, 6, 9];
+        let ratings_after = [10, 10, 9, 9, 8, 8, 6, 6, 9, 9];
+        let mut before = Survey::new();
+        let mut after = Survey::new();
+        for (respondent_id, (&first, &second)) in
+            ratings_before.iter().zip(ratings_after.iter()).enumerate()
+        {
+            before.add_response(respondent_id, first).unwrap();
+            after.add_response(respondent_id, second).unwrap();
+        }
+
+        let paired = compare_paired(&before, &after, 0.05).unwrap();
+        assert_close(paired.difference, 50.0);
+        assert!((paired.standard_error - 22.360_679_774_997_898).abs() < 1e-6);
+        assert!((paired.p_value - 0.025_347_318_677_468).abs() < 1e-6);
+        assert!(paired.significant);
+        assert_eq!(paired.paired_respondents, 10);
+
+        let independent = compare(&before, &after, 0.05).unwrap();
+        assert!((independent.standard_error - 38.441_875_315_569_32).abs() < 1e-6);
+        assert!(!independent.significant);
+    }
+
+    #[test]
+    fn test_compare_paired_without_overlap_matches_independent() {
+        let before = survey(&[(3, 5), (8, 5), (9, 5)]);
+        let mut after = Survey::new();
+        for respondent_id in 100..120 {
+            after.add_response(respondent_id, 10).unwrap();
+        }
+        after.add_response(120, 0).unwrap();
+
+        let paired = compare_paired(&before, &after, 0.05).unwrap();
+        let independent = compare(&before, &after, 0.05).unwrap();
+        assert_eq!(paired.paired_respondents, 0);
+        assert_close(paired.standard_error, independent.standard_error);
+    }
+
+    #[test]
+    fn test_compare_errors() {
+        let small = survey(&[(10, 1)]);
+        let large = survey(&[(10, 5), (0, 5)]);
+        assert_eq!(
+            compare(&small, &large, 0.05),
+            Err(NetPromoterScoreError::InsufficientResponses(1))
+        );
+        assert_eq!(
+            compare(&large, &large, 0.0),
+            Err(NetPromoterScoreError::InvalidSignificanceLevel)
+        );
+
+        let same = compare(&large, &large, 0.05).unwrap();
+        assert_eq!(same.z_statistic, 0.0);
+        assert!(!same.significant);
+    }
+
+    #[test]
+    fn test_interval_errors() {
+        assert_eq!(
+            COUNTS.confidence_interval(1.0, IntervalMethod::Wald),
+            Err(NetPromoterScoreError::InvalidConfidenceLevel)
+        );
+        let single = SegmentCounts {
+            promoters: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            single.confidence_interval(0.95, IntervalMethod::Trinomial),
+            Err(NetPromoterScoreError::InsufficientResponses(1))
+        );
+        assert!(SegmentCounts::default()
+            .confidence_interval(0.95, IntervalMethod::AdjustedWald)
+            .is_ok());
+    }
+}
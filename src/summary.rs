@@ -0,0 +1,276 @@
+//! A summary of a survey's counts, percentages, score and rating distribution.
+//!
+//! [`Survey::summary`](crate::Survey::summary) gathers everything a report usually needs into one
+//! [`NpsSummary`], computed once from the survey's rating histogram. It prints as a table with
+//! `Display`, serializes with stable field names under the `serde` feature, and
+//! [`delta`](NpsSummary::delta) compares it with an earlier summary.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//!
+//! let mut last_quarter = Survey::new();
+//! last_quarter.add_multiple_responses(vec![(1, 10), (2, 6), (3, 8), (4, 3)]).unwrap();
+//! let mut this_quarter = Survey::new();
+//! this_quarter.add_multiple_responses(vec![(1, 10), (2, 9), (3, 8), (4, 3)]).unwrap();
+//!
+//! let summary = this_quarter.summary();
+//! println!("{}", summary);
+//!
+//! let delta = summary.delta(&last_quarter.summary());
+//! assert_eq!(delta.score, 50);
+//! assert_eq!(
+//!     delta.to_string(),
+//!     "NPS +50 (+50.0), promoters +25.0 pts, passives +0.0 pts, detractors -25.0 pts, responses +0"
+//! );
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::histogram::ScoreHistogram;
+use crate::scale::ClassificationScheme;
+use crate::{RoundingPolicy, ScoreCount};
+
+/// The response counts, segment percentages, Net Promoter Score and rating distribution of a
+/// survey.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NpsSummary {
+    /// The total number of responses.
+    pub total: ScoreCount,
+    /// The number of promoters.
+    pub promoters: ScoreCount,
+    /// The number of passives.
+    pub passives: ScoreCount,
+    /// The number of detractors.
+    pub detractors: ScoreCount,
+    /// The percentage of responses from promoters, from 0 to 100.
+    pub promoter_percentage: f64,
+    /// The percentage of responses from passives, from 0 to 100.
+    pub passive_percentage: f64,
+    /// The percentage of responses from detractors, from 0 to 100.
+    pub detractor_percentage: f64,
+    /// The exact, unrounded Net Promoter Score.
+    pub score_exact: f64,
+    /// The Net Promoter Score rounded with the survey's `RoundingPolicy`.
+    pub score: i32,
+    /// The number of responses for each rating, indexed by rating.
+    pub distribution: [ScoreCount; 11],
+}
+
+impl NpsSummary {
+    /// Summarizes the ratings counted in `histogram`, classified with `scheme`, rounding the score
+    /// with `policy`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use net_promoter_score::prelude::*;
+    ///
+    /// let histogram = ScoreHistogram::from_counts([1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3]);
+    /// let summary = NpsSummary::from_histogram(&histogram, &NpsScheme, RoundingPolicy::HalfUp);
+    /// assert_eq!(summary.score, 50);
+    /// assert_eq!(summary.distribution[10], 3);
+    /// ```
+    pub fn from_histogram(
+        histogram: &ScoreHistogram,
+        scheme: &impl ClassificationScheme,
+        policy: RoundingPolicy,
+    ) -> Self {
+        let counts = histogram.segment_counts_with(scheme);
+        let percentage = |count: ScoreCount| match counts.total() {
+            0 => 0.0,
+            total => 100.0 * count as f64 / total as f64,
+        };
+        let score_exact = counts.score_exact();
+        Self {
+            total: counts.total(),
+            promoters: counts.promoters,
+            passives: counts.passives,
+            detractors: counts.detractors,
+            promoter_percentage: percentage(counts.promoters),
+            passive_percentage: percentage(counts.passives),
+            detractor_percentage: percentage(counts.detractors),
+            score_exact,
+            score: policy.round(score_exact) as i32,
+            distribution: *histogram.counts(),
+        }
+    }
+
+    /// Returns the change from `baseline` to this summary.
+    pub fn delta(&self, baseline: &NpsSummary) -> SummaryDelta {
+        let count = |current: ScoreCount, previous: ScoreCount| current as i64 - previous as i64;
+        SummaryDelta {
+            total: count(self.total, baseline.total),
+            promoters: count(self.promoters, baseline.promoters),
+            passives: count(self.passives, baseline.passives),
+            detractors: count(self.detractors, baseline.detractors),
+            promoter_percentage: self.promoter_percentage - baseline.promoter_percentage,
+            passive_percentage: self.passive_percentage - baseline.passive_percentage,
+            detractor_percentage: self.detractor_percentage - baseline.detractor_percentage,
+            score_exact: self.score_exact - baseline.score_exact,
+            score: self.score - baseline.score,
+        }
+    }
+}
+
+// Prints the segments as a table, followed by the score and the rating distribution.
+impl Display for NpsSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<10} {:>8} {:>8}", "Segment", "Count", "Percent")?;
+        for (segment, count, percentage) in [
+            ("Promoters", self.promoters, self.promoter_percentage),
+            ("Passives", self.passives, self.passive_percentage),
+            ("Detractors", self.detractors, self.detractor_percentage),
+        ] {
+            writeln!(f, "{:<10} {:>8} {:>7.1}%", segment, count, percentage)?;
+        }
+        writeln!(f, "{:<10} {:>8}", "Total", self.total)?;
+        writeln!(
+            f,
+            "{:<10} {:>8} {:>8.1}",
+            "NPS", self.score, self.score_exact
+        )?;
+
+        let width = self
+            .distribution
+            .iter()
+            .map(|count| count.to_string().len())
+            .max()
+            .unwrap_or(0)
+            .max(2);
+        write!(f, "{:<10}", "Rating")?;
+        for rating in 0..self.distribution.len() {
+            write!(f, " {:>width$}", rating, width = width)?;
+        }
+        write!(f, "\n{:<10}", "Count")?;
+        for count in self.distribution {
+            write!(f, " {:>width$}", count, width = width)?;
+        }
+        Ok(())
+    }
+}
+
+/// The change between two [`NpsSummary`] values, as returned by [`NpsSummary::delta`].
+///
+/// Counts and the score are differences in whole numbers, percentages are differences in
+/// percentage points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SummaryDelta {
+    /// The change in the total number of responses.
+    pub total: i64,
+    /// The change in the number of promoters.
+    pub promoters: i64,
+    /// The change in the number of passives.
+    pub passives: i64,
+    /// The change in the number of detractors.
+    pub detractors: i64,
+    /// The change in the percentage of promoters, in percentage points.
+    pub promoter_percentage: f64,
+    /// The change in the percentage of passives, in percentage points.
+    pub passive_percentage: f64,
+    /// The change in the percentage of detractors, in percentage points.
+    pub detractor_percentage: f64,
+    /// The change in the exact Net Promoter Score.
+    pub score_exact: f64,
+    /// The change in the rounded Net Promoter Score.
+    pub score: i32,
+}
+
+// Prints the signed changes on one line.
+impl Display for SummaryDelta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "NPS {:+} ({:+.1}), promoters {:+.1} pts, passives {:+.1} pts, detractors {:+.1} pts, responses {:+}",
+            self.score,
+            self.score_exact,
+            self.promoter_percentage,
+            self.passive_percentage,
+            self.detractor_percentage,
+            self.total
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_summary() {
+        let mut survey = Survey::new();
+        survey
+            .add_multiple_responses(vec![(1, 10), (2, 9), (3, 8), (4, 6), (5, 0), (6, 0)])
+            .unwrap();
+
+        let summary = survey.summary();
+        assert_eq!(summary.total, 6);
+        assert_eq!(
+            (summary.promoters, summary.passives, summary.detractors),
+            (2, 1, 3)
+        );
+        assert_eq!(summary.passive_percentage, 100.0 / 6.0);
+        assert_eq!(summary.score_exact, -100.0 / 6.0);
+        assert_eq!(summary.score, -17);
+        assert_eq!(summary.distribution, [2, 0, 0, 0, 0, 0, 1, 0, 1, 1, 1]);
+
+        let surveys = [survey, Survey::new()];
+        let scores: Vec<i32> = surveys.iter().map(Survey::score).collect();
+        assert_eq!(scores, vec![-17, 0]);
+
+        let empty = surveys[1].summary();
+        assert_eq!(empty.total, 0);
+        assert_eq!(empty.promoter_percentage, 0.0);
+    }
+
+    #[test]
+    fn test_summary_table() {
+        let mut survey = Survey::new();
+        survey
+            .add_bulk_responses_auto_id(&[(0, 2), (8, 1), (10, 120)])
+            .unwrap();
+
+        let expected = "\
+Segment       Count  Percent
+Promoters       120    97.6%
+Passives          1     0.8%
+Detractors        2     1.6%
+Total           123
+NPS              96     95.9
+Rating       0   1   2   3   4   5   6   7   8   9  10
+Count        2   0   0   0   0   0   0   0   1   0 120";
+        assert_eq!(survey.summary().to_string(), expected);
+    }
+
+    #[test]
+    fn test_summary_delta() {
+        let mut before = Survey::new();
+        before
+            .add_multiple_responses(vec![(1, 10), (2, 0)])
+            .unwrap();
+        let mut after = Survey::new();
+        after
+            .add_multiple_responses(vec![(1, 10), (2, 8), (3, 9)])
+            .unwrap();
+
+        let delta = after.summary().delta(&before.summary());
+        assert_eq!(
+            (
+                delta.total,
+                delta.promoters,
+                delta.passives,
+                delta.detractors
+            ),
+            (1, 1, 1, -1)
+        );
+        assert!((delta.score_exact - 200.0 / 3.0).abs() < 1e-9);
+        assert_eq!(delta.score, 67);
+        assert_eq!(
+            before.summary().delta(&before.summary()).to_string(),
+            "NPS +0 (+0.0), promoters +0.0 pts, passives +0.0 pts, detractors +0.0 pts, responses +0"
+        );
+    }
+}
@@ -0,0 +1,459 @@
+//! Keyword and n-gram analysis of response comments.
+//!
+//! [`Survey::comment_terms`](crate::Survey::comment_terms) splits every comment into lowercase
+//! words, drops common English stop words from the built-in [`STOP_WORDS`] list, and counts the
+//! remaining terms, or runs of `n` consecutive terms, separately for detractors, passives and
+//! promoters. Runs never cross punctuation or a dropped stop word.
+//! [`TermCounts::over_represented`] then surfaces the terms one classification uses much more
+//! often than another, such as what detractors complain about that promoters never mention.
+//! Everything runs locally without any external service or model.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//!
+//! let mut survey = Survey::new();
+//! survey.add_response_with_comment(1, 2, "The app is slow and support is slow to reply").unwrap();
+//! survey.add_response_with_comment(2, 4, "Slow checkout, too expensive").unwrap();
+//! survey.add_response_with_comment(3, 10, "Great support, great app").unwrap();
+//! survey.add_response_with_comment(4, 9, "Friendly support team").unwrap();
+//!
+//! let terms = survey.comment_terms(1);
+//! assert_eq!(terms.count(Classification::Detractor, "slow"), 3);
+//! assert_eq!(terms.top(Classification::Promoter, 2), vec![("great", 2), ("support", 2)]);
+//!
+//! let drivers = terms.over_represented(Classification::Detractor, Classification::Promoter, 1);
+//! assert_eq!(drivers[0].term, "slow");
+//!
+//! let phrases = survey.comment_terms(2);
+//! assert_eq!(phrases.count(Classification::Promoter, "friendly support"), 1);
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::scale::ClassificationScheme;
+use crate::{Classification, Survey};
+
+/// Common English words that carry little meaning on their own and are left out of term counts,
+/// in sorted order.
+///
+/// Negations such as `not`, `no` and `never` are deliberately kept, so that n-grams like
+/// `not helpful` survive.
+pub const STOP_WORDS: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "after",
+    "again",
+    "all",
+    "also",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "can",
+    "could",
+    "did",
+    "do",
+    "does",
+    "doing",
+    "down",
+    "during",
+    "each",
+    "even",
+    "few",
+    "for",
+    "from",
+    "further",
+    "get",
+    "got",
+    "had",
+    "has",
+    "have",
+    "having",
+    "he",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "i",
+    "i'd",
+    "i'm",
+    "i've",
+    "if",
+    "in",
+    "into",
+    "is",
+    "it",
+    "it's",
+    "its",
+    "itself",
+    "just",
+    "me",
+    "more",
+    "most",
+    "my",
+    "myself",
+    "of",
+    "off",
+    "on",
+    "once",
+    "only",
+    "or",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "really",
+    "same",
+    "she",
+    "should",
+    "so",
+    "some",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "under",
+    "until",
+    "up",
+    "us",
+    "very",
+    "was",
+    "we",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "would",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+/// Returns `true` if `word`, in lowercase, is in the built-in [`STOP_WORDS`] list.
+pub fn is_stop_word(word: &str) -> bool {
+    STOP_WORDS.binary_search(&word).is_ok()
+}
+
+/// Splits `text` into lowercase words and drops stop words.
+///
+/// Words are split as in [`words`].
+///
+/// # Example
+///
+/// ```
+/// use net_promoter_score::text::tokenize;
+///
+/// assert_eq!(tokenize("I don't like the NEW pricing!"), vec!["don't", "like", "new", "pricing"]);
+/// ```
+pub fn tokenize(text: &str) -> Vec<String> {
+    words(text).filter(|word| !is_stop_word(word)).collect()
+}
+
+/// Splits `text` into lowercase words, keeping stop words.
+///
+/// Words are runs of letters, digits and inner apostrophes, so `don't` stays one word while
+/// punctuation and surrounding quotes are removed. Typographic apostrophes are read as `'`.
+pub fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '\u{2019}'))
+        .map(|word| {
+            word.trim_matches(|c| c == '\'' || c == '\u{2019}')
+                .replace('\u{2019}', "'")
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+}
+
+/// Splits `text` into phrases: runs of words that are not stop words, broken at the clause
+/// punctuation `.`, `,`, `;`, `:`, `!` and `?` and wherever a stop word was dropped, so that
+/// n-grams of a phrase only join words that were next to each other.
+///
+/// Words are split as in [`words`].
+///
+/// # Example
+///
+/// ```
+/// use net_promoter_score::text::phrases;
+///
+/// assert_eq!(
+///     phrases("Slow checkout, too expensive. Not helpful at all"),
+///     vec![vec!["slow", "checkout"], vec!["expensive"], vec!["not", "helpful"]]
+/// );
+/// ```
+pub fn phrases(text: &str) -> Vec<Vec<String>> {
+    text.split(['.', ',', ';', ':', '!', '?'])
+        .flat_map(|clause| {
+            let words: Vec<String> = words(clause).collect();
+            words
+                .split(|word| is_stop_word(word))
+                .filter(|phrase| !phrase.is_empty())
+                .map(<[String]>::to_vec)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Returns every run of `n` consecutive `tokens`, joined with single spaces. An `n` of 0 is
+/// treated as 1.
+pub fn ngrams(tokens: &[String], n: usize) -> impl Iterator<Item = String> + '_ {
+    tokens.windows(n.max(1)).map(|window| window.join(" "))
+}
+
+/// Term counts of response comments, kept separately for each [`Classification`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TermCounts {
+    n: usize,
+    comments: BTreeMap<Classification, usize>,
+    terms: BTreeMap<Classification, BTreeMap<String, usize>>,
+}
+
+impl TermCounts {
+    /// Creates empty counts of terms made of `n` consecutive words. An `n` of 0 is treated as 1.
+    pub fn new(n: usize) -> Self {
+        Self {
+            n: n.max(1),
+            comments: BTreeMap::new(),
+            terms: BTreeMap::new(),
+        }
+    }
+
+    /// Counts the terms of one more comment left by a respondent with the given classification.
+    /// Terms of several words are taken from within each of the comment's [`phrases`].
+    pub fn add(&mut self, classification: Classification, comment: &str) {
+        *self.comments.entry(classification).or_default() += 1;
+        let terms = self.terms.entry(classification).or_default();
+        for phrase in phrases(comment) {
+            for term in ngrams(&phrase, self.n) {
+                *terms.entry(term).or_default() += 1;
+            }
+        }
+    }
+
+    /// Returns the number of words in each counted term.
+    pub fn ngram_size(&self) -> usize {
+        self.n
+    }
+
+    /// Returns the number of comments counted for `classification`.
+    pub fn comments(&self, classification: Classification) -> usize {
+        self.comments.get(&classification).copied().unwrap_or(0)
+    }
+
+    /// Returns the total number of term occurrences counted for `classification`.
+    pub fn total(&self, classification: Classification) -> usize {
+        self.terms(classification).map(|(_, count)| count).sum()
+    }
+
+    /// Returns the number of times `term` occurs in the comments of `classification`.
+    pub fn count(&self, classification: Classification, term: &str) -> usize {
+        self.terms
+            .get(&classification)
+            .and_then(|terms| terms.get(term))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Iterates over the terms of `classification` and their counts, in alphabetical order.
+    pub fn terms(&self, classification: Classification) -> impl Iterator<Item = (&str, usize)> {
+        self.terms
+            .get(&classification)
+            .into_iter()
+            .flatten()
+            .map(|(term, count)| (term.as_str(), *count))
+    }
+
+    /// Returns up to `limit` of the most frequent terms of `classification`, most frequent first.
+    /// Ties are broken alphabetically.
+    pub fn top(&self, classification: Classification, limit: usize) -> Vec<(&str, usize)> {
+        let mut terms: Vec<_> = self.terms(classification).collect();
+        terms.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        terms.truncate(limit);
+        terms
+    }
+
+    /// Returns up to `limit` terms used relatively more often in the comments of `target` than in
+    /// those of `baseline`, most over-represented first.
+    ///
+    /// Each term's share of all term occurrences is compared between the two classifications,
+    /// with add-one smoothing so that terms absent from `baseline` get a finite ratio that still
+    /// grows with how often `target` uses them. Only terms that occur in `target` with a ratio
+    /// above 1 are returned.
+    pub fn over_represented(
+        &self,
+        target: Classification,
+        baseline: Classification,
+        limit: usize,
+    ) -> Vec<TermContrast> {
+        let vocabulary = self
+            .terms(target)
+            .chain(self.terms(baseline))
+            .map(|(term, _)| term)
+            .collect::<BTreeSet<_>>()
+            .len() as f64;
+        let target_total = self.total(target) as f64 + vocabulary;
+        let baseline_total = self.total(baseline) as f64 + vocabulary;
+
+        let mut contrasts: Vec<_> = self
+            .terms(target)
+            .map(|(term, target_count)| {
+                let baseline_count = self.count(baseline, term);
+                let ratio = ((target_count + 1) as f64 / target_total)
+                    / ((baseline_count + 1) as f64 / baseline_total);
+                TermContrast {
+                    term: term.to_owned(),
+                    target_count,
+                    baseline_count,
+                    ratio,
+                }
+            })
+            .filter(|contrast| contrast.ratio > 1.0)
+            .collect();
+        contrasts.sort_by(|a, b| {
+            b.ratio
+                .total_cmp(&a.ratio)
+                .then(b.target_count.cmp(&a.target_count))
+                .then_with(|| a.term.cmp(&b.term))
+        });
+        contrasts.truncate(limit);
+        contrasts
+    }
+}
+
+/// How much more often one classification uses a term than another, as returned by
+/// [`TermCounts::over_represented`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TermContrast {
+    /// The term.
+    pub term: String,
+    /// The number of times the term occurs in the target classification's comments.
+    pub target_count: usize,
+    /// The number of times the term occurs in the baseline classification's comments.
+    pub baseline_count: usize,
+    /// The smoothed ratio of the term's share of target occurrences to its share of baseline
+    /// occurrences.
+    pub ratio: f64,
+}
+
+impl<T: PartialEq, S: ClassificationScheme> Survey<T, S> {
+    /// Counts the terms of `n` consecutive words in the comments of the current responses, per
+    /// classification. Use `1` for single keywords, `2` for bigrams and so on.
+    ///
+    /// Responses without a comment are skipped.
+    pub fn comment_terms(&self, n: usize) -> TermCounts {
+        let mut counts = TermCounts::new(n);
+        for response in self.responses.values() {
+            if let Some(comment) = response.comment() {
+                counts.add(self.scheme.classify(*response.score()), comment);
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_words_are_sorted() {
+        assert!(STOP_WORDS.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(is_stop_word("the"));
+        assert!(!is_stop_word("not"));
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("It\u{2019}s 'really' NOT worth $30/month..."),
+            vec!["not", "worth", "30", "month"]
+        );
+        assert!(tokenize("  ...  ").is_empty());
+        let tokens = tokenize("not helpful at all today");
+        assert_eq!(
+            ngrams(&tokens, 2).collect::<Vec<_>>(),
+            vec!["not helpful", "helpful today"]
+        );
+        assert_eq!(ngrams(&tokens, 4).count(), 0);
+
+        let mut bigrams = TermCounts::new(2);
+        bigrams.add(Classification::Detractor, "not helpful at all today");
+        bigrams.add(Classification::Detractor, "Slow checkout, too expensive");
+        assert_eq!(
+            bigrams
+                .terms(Classification::Detractor)
+                .map(|(term, _)| term)
+                .collect::<Vec<_>>(),
+            vec!["not helpful", "slow checkout"]
+        );
+    }
+
+    #[test]
+    fn test_over_represented() {
+        let mut counts = TermCounts::new(1);
+        counts.add(Classification::Detractor, "billing error, billing again");
+        counts.add(Classification::Detractor, "support slow");
+        counts.add(Classification::Promoter, "support great");
+        counts.add(Classification::Promoter, "support fast, great value");
+
+        assert_eq!(counts.comments(Classification::Detractor), 2);
+        assert_eq!(counts.total(Classification::Detractor), 5);
+        assert_eq!(counts.comments(Classification::Passive), 0);
+
+        let contrasts =
+            counts.over_represented(Classification::Detractor, Classification::Promoter, 10);
+        let terms: Vec<_> = contrasts.iter().map(|c| c.term.as_str()).collect();
+        assert_eq!(terms, vec!["billing", "error", "slow"]);
+        assert_eq!(contrasts[0].target_count, 2);
+        assert_eq!(contrasts[0].baseline_count, 0);
+        assert!(contrasts[0].ratio > contrasts[1].ratio);
+        assert!(counts
+            .over_represented(Classification::Passive, Classification::Promoter, 10)
+            .is_empty());
+    }
+}
@@ -0,0 +1,404 @@
+//! Rule-based theme tagging of response comments. Requires the `themes` feature.
+//!
+//! A [`Taxonomy`] lists themes such as pricing, support or onboarding, each with keywords and
+//! regular expression patterns, and is usually loaded from TOML or JSON. A [`ThemeTagger`]
+//! compiles the taxonomy and tags comments with every theme whose rules match.
+//! [`Survey::theme_matrix`](crate::Survey::theme_matrix) counts the detractors, passives and
+//! promoters who mentioned each theme, which shows the themes that drive detraction.
+//!
+//! Keywords match whole words or phrases, ignoring case. Patterns are applied as written, so use
+//! `(?i)` for case-insensitive patterns.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//! use net_promoter_score::themes::ThemeTagger;
+//!
+//! let tagger = ThemeTagger::from_toml(
+//!     r#"
+//!     [[themes]]
+//!     name = "pricing"
+//!     keywords = ["price", "expensive", "too costly"]
+//!     patterns = ["(?i)over-?priced"]
+//!
+//!     [[themes]]
+//!     name = "support"
+//!     keywords = ["support", "help desk"]
+//!     "#,
+//! )
+//! .unwrap();
+//!
+//! let mut survey = Survey::new();
+//! survey.add_response_with_comment(1, 2, "Overpriced, and support ignored me").unwrap();
+//! survey.add_response_with_comment(2, 5, "The price went up again").unwrap();
+//! survey.add_response_with_comment(3, 10, "Support sorted it out in minutes").unwrap();
+//! survey.add_response_with_comment(4, 9, "Love it").unwrap();
+//!
+//! let matrix = survey.theme_matrix(&tagger);
+//! assert_eq!(matrix.get("pricing").unwrap().detractors, 2);
+//! assert_eq!(matrix.get("support").unwrap().score_exact(), 0.0);
+//! assert_eq!(matrix.untagged.promoters, 1);
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+
+use regex::Regex;
+
+use crate::scale::ClassificationScheme;
+use crate::{SegmentCounts, Survey};
+
+/// A set of themes to tag comments with, in the order they are reported.
+///
+/// In TOML, each theme is a `[[themes]]` table; in JSON, the taxonomy is an object with a `themes`
+/// array. `keywords` and `patterns` may be left out.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct Taxonomy {
+    /// The themes of the taxonomy.
+    pub themes: Vec<Theme>,
+}
+
+impl Taxonomy {
+    /// Reads a taxonomy from TOML.
+    pub fn from_toml(input: &str) -> Result<Self, ThemeError> {
+        toml::from_str(input).map_err(|error| ThemeError::Parse(error.to_string()))
+    }
+
+    /// Reads a taxonomy from JSON.
+    pub fn from_json(input: &str) -> Result<Self, ThemeError> {
+        serde_json::from_str(input).map_err(|error| ThemeError::Parse(error.to_string()))
+    }
+}
+
+/// A theme and the rules that tag a comment with it. A comment is tagged if any rule matches.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct Theme {
+    /// The name of the theme, unique within its taxonomy.
+    pub name: String,
+    /// Words or phrases that tag a comment when they occur as whole words, ignoring case.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Regular expressions that tag a comment when they match anywhere in it.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+/// Tags comments with the themes of a compiled [`Taxonomy`].
+#[derive(Debug, Clone)]
+pub struct ThemeTagger {
+    // Each theme's name with its keywords and patterns compiled together.
+    rules: Vec<(String, Vec<Regex>)>,
+}
+
+impl ThemeTagger {
+    /// Compiles the rules of `taxonomy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ThemeError::DuplicateTheme`] if two themes share a name, or
+    /// [`ThemeError::InvalidPattern`] if a pattern is not a valid regular expression.
+    pub fn new(taxonomy: Taxonomy) -> Result<Self, ThemeError> {
+        let mut rules: Vec<(String, Vec<Regex>)> = Vec::with_capacity(taxonomy.themes.len());
+        for theme in taxonomy.themes {
+            if rules.iter().any(|(name, _)| *name == theme.name) {
+                return Err(ThemeError::DuplicateTheme(theme.name));
+            }
+            let mut regexes = Vec::new();
+            let keywords: Vec<String> = theme
+                .keywords
+                .iter()
+                .map(|keyword| keyword.split_whitespace().collect::<Vec<_>>())
+                .filter(|words| !words.is_empty())
+                .map(|words| {
+                    words
+                        .iter()
+                        .map(|word| regex::escape(word))
+                        .collect::<Vec<_>>()
+                        .join(r"\s+")
+                })
+                .collect();
+            if !keywords.is_empty() {
+                let keywords = format!(r"(?i)\b(?:{})\b", keywords.join("|"));
+                regexes.push(Regex::new(&keywords).expect("escaped keywords form a valid regex"));
+            }
+            for pattern in &theme.patterns {
+                let regex = Regex::new(pattern).map_err(|error| ThemeError::InvalidPattern {
+                    theme: theme.name.clone(),
+                    pattern: pattern.clone(),
+                    message: error.to_string(),
+                })?;
+                regexes.push(regex);
+            }
+            rules.push((theme.name, regexes));
+        }
+        Ok(Self { rules })
+    }
+
+    /// Reads a taxonomy from TOML and compiles it.
+    pub fn from_toml(input: &str) -> Result<Self, ThemeError> {
+        Self::new(Taxonomy::from_toml(input)?)
+    }
+
+    /// Reads a taxonomy from JSON and compiles it.
+    pub fn from_json(input: &str) -> Result<Self, ThemeError> {
+        Self::new(Taxonomy::from_json(input)?)
+    }
+
+    /// Returns the names of the themes, in taxonomy order.
+    pub fn themes(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the names of the themes whose rules match `comment`, in taxonomy order.
+    pub fn tag(&self, comment: &str) -> Vec<&str> {
+        self.rules
+            .iter()
+            .filter(|(_, regexes)| regexes.iter().any(|regex| regex.is_match(comment)))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+/// The detractors, passives and promoters who mentioned each theme, as returned by
+/// [`Survey::theme_matrix`].
+///
+/// A comment tagged with several themes is counted under each of them.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ThemeMatrix {
+    /// The segment counts of each theme, in taxonomy order. Themes no comment matched have zero
+    /// counts.
+    pub themes: Vec<(String, SegmentCounts)>,
+    /// The segment counts of comments that matched no theme.
+    pub untagged: SegmentCounts,
+}
+
+impl ThemeMatrix {
+    /// Returns the segment counts of `theme`, if it is part of the taxonomy.
+    pub fn get(&self, theme: &str) -> Option<&SegmentCounts> {
+        self.themes
+            .iter()
+            .find(|(name, _)| name == theme)
+            .map(|(_, counts)| counts)
+    }
+
+    /// Returns the themes that were mentioned at least once, lowest Net Promoter Score first, so
+    /// the themes driving detraction come first. Ties keep taxonomy order.
+    pub fn by_score(&self) -> Vec<(&str, &SegmentCounts)> {
+        let mut themes: Vec<_> = self
+            .themes
+            .iter()
+            .filter(|(_, counts)| counts.total() > 0)
+            .map(|(name, counts)| (name.as_str(), counts))
+            .collect();
+        themes.sort_by(|a, b| a.1.score_exact().total_cmp(&b.1.score_exact()));
+        themes
+    }
+}
+
+// Prints one row per theme with its segment counts and exact NPS, followed by untagged comments.
+impl Display for ThemeMatrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let width = self
+            .themes
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("(untagged)".len());
+        write!(
+            f,
+            "{:<width$} {:>10} {:>8} {:>9} {:>6} {:>6}",
+            "Theme",
+            "Detractors",
+            "Passives",
+            "Promoters",
+            "Total",
+            "NPS",
+            width = width
+        )?;
+        let rows = self
+            .themes
+            .iter()
+            .map(|(name, counts)| (name.as_str(), counts));
+        for (name, counts) in rows.chain([("(untagged)", &self.untagged)]) {
+            write!(
+                f,
+                "\n{:<width$} {:>10} {:>8} {:>9} {:>6} {:>6.1}",
+                name,
+                counts.detractors,
+                counts.passives,
+                counts.promoters,
+                counts.total(),
+                counts.score_exact(),
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: PartialEq, S: ClassificationScheme> Survey<T, S> {
+    /// Tags the comments of the current responses with `tagger` and counts the detractors,
+    /// passives and promoters who mentioned each theme. Requires the `themes` feature.
+    ///
+    /// Responses without a comment are skipped.
+    pub fn theme_matrix(&self, tagger: &ThemeTagger) -> ThemeMatrix {
+        let mut matrix = ThemeMatrix {
+            themes: tagger
+                .themes()
+                .map(|name| (name.to_owned(), SegmentCounts::default()))
+                .collect(),
+            untagged: SegmentCounts::default(),
+        };
+        for response in self.responses.values() {
+            let Some(comment) = response.comment() else {
+                continue;
+            };
+            let classification = self.scheme.classify(*response.score());
+            let mut tagged = false;
+            for ((_, regexes), (_, counts)) in tagger.rules.iter().zip(&mut matrix.themes) {
+                if regexes.iter().any(|regex| regex.is_match(comment)) {
+                    counts.record(classification);
+                    tagged = true;
+                }
+            }
+            if !tagged {
+                matrix.untagged.record(classification);
+            }
+        }
+        matrix
+    }
+}
+
+/// An error loading or compiling a [`Taxonomy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeError {
+    /// The TOML or JSON input could not be read as a taxonomy.
+    Parse(String),
+    /// Two themes share this name.
+    DuplicateTheme(String),
+    /// A pattern is not a valid regular expression.
+    InvalidPattern {
+        /// The theme the pattern belongs to.
+        theme: String,
+        /// The pattern as written.
+        pattern: String,
+        /// Why the pattern is invalid.
+        message: String,
+    },
+}
+
+impl std::error::Error for ThemeError {}
+
+impl Display for ThemeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Parse(message) => write!(f, "invalid taxonomy: {}", message),
+            ThemeError::DuplicateTheme(name) => write!(f, "duplicate theme \"{}\"", name),
+            ThemeError::InvalidPattern {
+                theme,
+                pattern,
+                message,
+            } => write!(
+                f,
+                "invalid pattern \"{}\" in theme \"{}\": {}",
+                pattern, theme, message
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TAXONOMY: &str = r#"{
+        "themes": [
+            { "name": "pricing", "keywords": ["price", "too  expensive"] },
+            { "name": "performance", "keywords": ["slow"], "patterns": ["\\b\\d+\\s*s(ec)?\\b"] },
+            { "name": "onboarding" }
+        ]
+    }"#;
+
+    #[test]
+    fn test_tag() {
+        let tagger = ThemeTagger::from_json(TAXONOMY).unwrap();
+        assert_eq!(
+            tagger.themes().collect::<Vec<_>>(),
+            vec!["pricing", "performance", "onboarding"]
+        );
+        assert_eq!(
+            tagger.tag("Way TOO\nexpensive and pages take 12s"),
+            vec!["pricing", "performance"]
+        );
+        // Keywords only match whole words.
+        assert!(tagger
+            .tag("The prices are fine, nothing slowed me down")
+            .is_empty());
+        assert_eq!(tagger.tag("SLOW!"), vec!["performance"]);
+    }
+
+    #[test]
+    fn test_theme_matrix() {
+        let tagger = ThemeTagger::from_json(TAXONOMY).unwrap();
+        let mut survey = Survey::new();
+        survey.add_response_with_comment(1, 0, "slow").unwrap();
+        survey
+            .add_response_with_comment(2, 3, "slow, bad price")
+            .unwrap();
+        survey
+            .add_response_with_comment(3, 7, "fair price")
+            .unwrap();
+        survey
+            .add_response_with_comment(4, 10, "good price")
+            .unwrap();
+        survey.add_response_with_comment(5, 10, "great").unwrap();
+        survey.add_response(6, 0).unwrap();
+
+        let matrix = survey.theme_matrix(&tagger);
+        assert_eq!(matrix.get("performance").unwrap().detractors, 2);
+        assert_eq!(matrix.get("pricing").unwrap().total(), 3);
+        assert_eq!(matrix.get("onboarding").unwrap().total(), 0);
+        assert!(matrix.get("billing").is_none());
+        assert_eq!(matrix.untagged.total(), 1);
+
+        let ranked: Vec<_> = matrix.by_score().iter().map(|(name, _)| *name).collect();
+        assert_eq!(ranked, vec!["performance", "pricing"]);
+
+        let expected = "\
+Theme       Detractors Passives Promoters  Total    NPS
+pricing              1        1         1      3    0.0
+performance          2        0         0      2 -100.0
+onboarding           0        0         0      0    0.0
+(untagged)           0        0         1      1  100.0";
+        assert_eq!(matrix.to_string(), expected);
+    }
+
+    #[test]
+    fn test_taxonomy_errors() {
+        let duplicate = r#"
+            [[themes]]
+            name = "support"
+            [[themes]]
+            name = "support"
+        "#;
+        assert_eq!(
+            ThemeTagger::from_toml(duplicate).unwrap_err(),
+            ThemeError::DuplicateTheme("support".to_string())
+        );
+
+        let invalid = r#"{ "themes": [{ "name": "billing", "patterns": ["(unclosed"] }] }"#;
+        let error = ThemeTagger::from_json(invalid).unwrap_err();
+        assert!(
+            matches!(error, ThemeError::InvalidPattern { ref theme, .. } if theme == "billing")
+        );
+        assert!(error
+            .to_string()
+            .starts_with("invalid pattern \"(unclosed\" in theme \"billing\""));
+
+        assert!(matches!(
+            Taxonomy::from_toml("themes = 3"),
+            Err(ThemeError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,398 @@
+//! Time-windowed Net Promoter Scores.
+//!
+//! Responses added with a timestamp, e.g. through [`Survey::add_response_at`](crate::Survey::add_response_at), can be bucketed
+//! into calendar periods with [`Survey::time_series`](crate::Survey::time_series). Buckets are aligned to UTC calendar
+//! boundaries: days start at midnight, weeks on Monday, months on the 1st and quarters on the 1st
+//! of January, April, July and October. Responses without a timestamp are ignored, as are
+//! responses so far from the epoch that the bounds of their bucket can't be represented as a
+//! `SystemTime`.
+//!
+//! # Example
+//!
+//! ```
+//! use net_promoter_score::prelude::*;
+//! use std::time::{Duration, UNIX_EPOCH};
+//!
+//! let day = |n: u64| UNIX_EPOCH + Duration::from_secs(n * 86_400);
+//! let mut survey = Survey::new();
+//! survey
+//!     .add_multiple_responses_at(vec![(1, 10, day(0)), (2, 3, day(0)), (3, 9, day(2))])
+//!     .unwrap();
+//!
+//! let daily: Vec<TimeBucket> = survey.time_series(Period::Day).collect();
+//! assert_eq!(daily.len(), 3);
+//! assert_eq!(daily[0].nps, Some(0.0));
+//! assert_eq!(daily[1].nps, None);
+//! assert_eq!(daily[2].nps, Some(100.0));
+//!
+//! let rolling: Vec<TimeBucket> = survey.time_series(Period::Day).rolling(3).collect();
+//! assert_eq!(rolling[2].counts.total(), 3);
+//! ```
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Classification, Rating, SegmentCounts, SurveyResponse};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// A calendar period used to bucket responses by their timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Period {
+    /// A UTC calendar day.
+    Day,
+    /// An ISO week, starting on Monday.
+    Week,
+    /// A calendar month.
+    Month,
+    /// A calendar quarter, starting in January, April, July or October.
+    Quarter,
+}
+
+impl Period {
+    /// Returns the first day (counted from the Unix epoch) of the period containing `day`.
+    fn start_of(self, day: i64) -> i64 {
+        match self {
+            Period::Day => day,
+            // 1970-01-01 was a Thursday, three days after Monday.
+            Period::Week => day - (day + 3).rem_euclid(7),
+            Period::Month => {
+                let (year, month, _) = civil_from_days(day);
+                days_from_civil(year, month, 1)
+            }
+            Period::Quarter => {
+                let (year, month, _) = civil_from_days(day);
+                days_from_civil(year, month - (month - 1) % 3, 1)
+            }
+        }
+    }
+
+    /// Returns the first day of the period following the one starting on `start`.
+    fn next(self, start: i64) -> i64 {
+        match self {
+            Period::Day => start + 1,
+            Period::Week => start + 7,
+            Period::Month | Period::Quarter => {
+                let (year, month, _) = civil_from_days(start);
+                let months = if self == Period::Month { 1 } else { 3 };
+                let index = year * 12 + (month as i64 - 1) + months;
+                days_from_civil(index.div_euclid(12), (index.rem_euclid(12) + 1) as u32, 1)
+            }
+        }
+    }
+}
+
+/// The responses that fall within one time bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeBucket {
+    /// The inclusive start of the bucket.
+    pub start: SystemTime,
+    /// The exclusive end of the bucket.
+    pub end: SystemTime,
+    /// The number of detractors, passives and promoters in the bucket.
+    pub counts: SegmentCounts,
+    /// The exact Net Promoter Score of the bucket, or `None` if it has no responses.
+    pub nps: Option<f64>,
+}
+
+impl TimeBucket {
+    fn new(start: i64, end: i64, counts: SegmentCounts) -> Option<Self> {
+        Some(Self {
+            start: time_from_days(start)?,
+            end: time_from_days(end)?,
+            counts,
+            nps: (counts.total() > 0).then(|| counts.score_exact()),
+        })
+    }
+}
+
+/// An iterator over consecutive time buckets, from the bucket holding the earliest timestamped
+/// response to the bucket holding the latest. Buckets without responses are included.
+///
+/// Created by [`Survey::time_series`](crate::Survey::time_series).
+#[derive(Debug, Clone)]
+pub struct TimeSeries {
+    period: Period,
+    counts: BTreeMap<i64, SegmentCounts>,
+    next: Option<i64>,
+    last: i64,
+}
+
+impl TimeSeries {
+    pub(crate) fn new<'a, T: PartialEq + 'a>(
+        period: Period,
+        responses: impl Iterator<Item = &'a SurveyResponse<T>>,
+        classify: impl Fn(Rating) -> Classification,
+    ) -> Self {
+        let mut counts: BTreeMap<i64, SegmentCounts> = BTreeMap::new();
+        for response in responses {
+            if let Some(timestamp) = response.timestamp() {
+                let start = period.start_of(days_since_epoch(timestamp));
+                if time_from_days(start).is_none() || time_from_days(period.next(start)).is_none() {
+                    continue;
+                }
+                counts
+                    .entry(start)
+                    .or_default()
+                    .record(classify(*response.score()));
+            }
+        }
+        let next = counts.keys().next().copied();
+        let last = counts.keys().next_back().copied().unwrap_or_default();
+        Self {
+            period,
+            counts,
+            next,
+            last,
+        }
+    }
+
+    /// Turns this series into a rolling series, where each bucket holds the combined responses of
+    /// itself and up to `window - 1` preceding buckets. A `window` of 0 is treated as 1.
+    ///
+    /// The `start` of each rolling bucket is the start of the earliest bucket in its window.
+    pub fn rolling(self, window: usize) -> RollingTimeSeries {
+        RollingTimeSeries {
+            series: self,
+            window: window.max(1),
+            buckets: VecDeque::new(),
+        }
+    }
+}
+
+impl Iterator for TimeSeries {
+    type Item = TimeBucket;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.next.filter(|&start| start <= self.last)?;
+        let end = self.period.next(start);
+        self.next = Some(end);
+        let counts = self.counts.get(&start).copied().unwrap_or_default();
+        TimeBucket::new(start, end, counts)
+    }
+}
+
+/// An iterator over rolling time windows. Created by [`TimeSeries::rolling`].
+#[derive(Debug, Clone)]
+pub struct RollingTimeSeries {
+    series: TimeSeries,
+    window: usize,
+    buckets: VecDeque<TimeBucket>,
+}
+
+impl Iterator for RollingTimeSeries {
+    type Item = TimeBucket;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bucket = self.series.next()?;
+        if self.buckets.len() == self.window {
+            self.buckets.pop_front();
+        }
+        self.buckets.push_back(bucket);
+
+        let counts = self
+            .buckets
+            .iter()
+            .fold(SegmentCounts::default(), |total, bucket| {
+                total + bucket.counts
+            });
+        Some(TimeBucket {
+            start: self.buckets[0].start,
+            end: bucket.end,
+            counts,
+            nps: (counts.total() > 0).then(|| counts.score_exact()),
+        })
+    }
+}
+
+/// Returns the number of whole days between the Unix epoch and `time`, rounding toward the past.
+pub(crate) fn days_since_epoch(time: SystemTime) -> i64 {
+    let seconds = match time.duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => elapsed.as_secs() as i64,
+        Err(error) => {
+            let before = error.duration();
+            -(before.as_secs() as i64) - i64::from(before.subsec_nanos() > 0)
+        }
+    };
+    seconds.div_euclid(SECONDS_PER_DAY)
+}
+
+/// Returns midnight UTC at the start of the given day, counted from the Unix epoch, or `None` if
+/// it can't be represented as a `SystemTime`.
+pub(crate) fn time_from_days(days: i64) -> Option<SystemTime> {
+    let offset = Duration::from_secs(days.unsigned_abs().checked_mul(SECONDS_PER_DAY as u64)?);
+    if days >= 0 {
+        UNIX_EPOCH.checked_add(offset)
+    } else {
+        UNIX_EPOCH.checked_sub(offset)
+    }
+}
+
+/// Returns the number of days between the Unix epoch and the given proleptic Gregorian date.
+/// Uses Howard Hinnant's `days_from_civil` algorithm.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the proleptic Gregorian `(year, month, day)` of the given day since the Unix epoch.
+/// Uses Howard Hinnant's `civil_from_days` algorithm.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Survey;
+
+    fn date(year: i64, month: u32, day: u32) -> SystemTime {
+        time_from_days(days_from_civil(year, month, day)).unwrap()
+    }
+
+    #[test]
+    fn test_civil_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        for days in -800_000..800_000 {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn test_days_since_epoch_before_epoch() {
+        assert_eq!(days_since_epoch(UNIX_EPOCH - Duration::from_secs(1)), -1);
+        assert_eq!(
+            days_since_epoch(UNIX_EPOCH - Duration::from_secs(86_400)),
+            -1
+        );
+        assert_eq!(
+            days_since_epoch(UNIX_EPOCH + Duration::from_secs(86_399)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_period_boundaries() {
+        // 2024-02-29 was a Thursday
+        let day = days_from_civil(2024, 2, 29);
+        assert_eq!(Period::Day.start_of(day), day);
+        assert_eq!(Period::Week.start_of(day), days_from_civil(2024, 2, 26));
+        assert_eq!(Period::Month.start_of(day), days_from_civil(2024, 2, 1));
+        assert_eq!(Period::Quarter.start_of(day), days_from_civil(2024, 1, 1));
+
+        assert_eq!(
+            Period::Month.next(days_from_civil(2024, 12, 1)),
+            days_from_civil(2025, 1, 1)
+        );
+        assert_eq!(
+            Period::Quarter.next(days_from_civil(2024, 10, 1)),
+            days_from_civil(2025, 1, 1)
+        );
+        assert_eq!(
+            Period::Week.start_of(days_from_civil(2024, 3, 3)),
+            days_from_civil(2024, 2, 26)
+        );
+    }
+
+    #[test]
+    fn test_monthly_series_with_gaps() {
+        let mut survey = Survey::new();
+        survey
+            .add_multiple_responses_at(vec![
+                (1, 10, date(2024, 1, 5)),
+                (2, 2, date(2024, 1, 31)),
+                (3, 9, date(2024, 1, 31)),
+                (4, 7, date(2024, 4, 1)),
+            ])
+            .unwrap();
+        survey.add_response(5, 0).unwrap();
+
+        let buckets: Vec<TimeBucket> = survey.time_series(Period::Month).collect();
+        assert_eq!(buckets.len(), 4);
+        assert_eq!(buckets[0].start, date(2024, 1, 1));
+        assert_eq!(buckets[0].end, date(2024, 2, 1));
+        assert_eq!(
+            buckets[0].counts,
+            SegmentCounts {
+                detractors: 1,
+                passives: 0,
+                promoters: 2
+            }
+        );
+        assert!((buckets[0].nps.unwrap() - 100.0 / 3.0).abs() < 1e-9);
+        assert_eq!(buckets[1].nps, None);
+        assert_eq!(buckets[2].counts.total(), 0);
+        assert_eq!(buckets[3].start, date(2024, 4, 1));
+        assert_eq!(buckets[3].nps, Some(0.0));
+
+        let quarters: Vec<TimeBucket> = survey.time_series(Period::Quarter).collect();
+        assert_eq!(quarters.len(), 2);
+        assert_eq!(quarters[1].end, date(2024, 7, 1));
+    }
+
+    #[test]
+    fn test_rolling_series() {
+        let mut survey = Survey::new();
+        for (respondent_id, day) in (1..=5).zip(1..=5) {
+            let rating = if day % 2 == 0 { 0 } else { 10 };
+            survey
+                .add_response_at(respondent_id, rating, date(2024, 5, day))
+                .unwrap();
+        }
+
+        let rolling: Vec<TimeBucket> = survey.time_series(Period::Day).rolling(2).collect();
+        assert_eq!(rolling.len(), 5);
+        assert_eq!(rolling[0].counts.total(), 1);
+        assert_eq!(rolling[0].nps, Some(100.0));
+        assert_eq!(rolling[1].start, date(2024, 5, 1));
+        assert_eq!(rolling[1].end, date(2024, 5, 3));
+        assert_eq!(rolling[1].nps, Some(0.0));
+        assert_eq!(rolling[4].start, date(2024, 5, 4));
+        assert_eq!(rolling[4].counts.total(), 2);
+    }
+
+    #[test]
+    fn test_empty_series() {
+        let mut survey = Survey::new();
+        survey.add_response(1, 10).unwrap();
+        assert_eq!(survey.time_series(Period::Week).count(), 0);
+        assert_eq!(survey.time_series(Period::Week).rolling(0).count(), 0);
+    }
+
+    #[test]
+    fn test_unrepresentable_buckets_are_skipped() {
+        let mut survey = Survey::new();
+        let far = Duration::from_secs(i64::MAX as u64);
+        survey.add_response_at(1, 10, UNIX_EPOCH + far).unwrap();
+        survey.add_response_at(2, 0, UNIX_EPOCH - far).unwrap();
+        survey.add_response_at(3, 9, date(2024, 5, 1)).unwrap();
+
+        let monthly: Vec<TimeBucket> = survey.time_series(Period::Month).collect();
+        assert_eq!(monthly.len(), 1);
+        assert_eq!(monthly[0].start, date(2024, 5, 1));
+        assert_eq!(monthly[0].counts.total(), 1);
+    }
+}
@@ -0,0 +1,143 @@
+//! Regression tests checking that the cached score of a `Survey` always matches a survey rebuilt
+//! from scratch, whatever order its mutation methods are called in.
+use net_promoter_score::prelude::*;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// A small xorshift generator, so the interleavings are random but reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound
+    }
+
+    fn rating(&mut self) -> NpsRating {
+        self.next(11) as NpsRating
+    }
+}
+
+fn fresh_copy(survey: &Survey<u32>) -> Survey<u32> {
+    let responses: Vec<(u32, NpsRating)> = survey
+        .responses()
+        .map(|response| (*response.respondent_id(), **response.score()))
+        .collect();
+    Survey::from_responses::<NetPromoterScoreError>(responses).unwrap()
+}
+
+fn recount(survey: &Survey<u32>) -> SegmentCounts {
+    survey
+        .responses()
+        .fold(SegmentCounts::default(), |mut counts, response| {
+            counts.record(response.score().into());
+            counts
+        })
+}
+
+fn apply_random_operation(survey: &mut Survey<u32>, rng: &mut Rng) {
+    let respondent_id = rng.next(40) as u32;
+    match rng.next(12) {
+        0 => {
+            survey.add_response(respondent_id, rng.rating()).unwrap();
+        }
+        1 => {
+            let timestamp = UNIX_EPOCH + Duration::from_secs(rng.next(1_000_000));
+            survey
+                .add_response_at(respondent_id, rng.rating(), timestamp)
+                .unwrap();
+        }
+        2 => {
+            survey
+                .add_response_with_attributes(respondent_id, rng.rating(), [("region", "EMEA")])
+                .unwrap();
+        }
+        3 => {
+            let responses: Vec<(u32, NpsRating)> = (0..rng.next(5))
+                .map(|_| (rng.next(40) as u32, rng.rating()))
+                .collect();
+            survey.add_multiple_responses(responses).unwrap();
+        }
+        4 => {
+            let mut next_id = rng.next(40) as u32;
+            let id_fn = || {
+                next_id = (next_id + 1) % 40;
+                next_id
+            };
+            let quantities = [(rng.rating(), rng.next(3) as usize)];
+            survey.add_bulk_responses(id_fn, &quantities).unwrap();
+        }
+        5 => {
+            let response = SurveyResponse::new(respondent_id, rng.rating()).unwrap();
+            survey.extend(vec![(respondent_id, response)]);
+        }
+        6 => {
+            let response = SurveyResponse::new(respondent_id, rng.rating()).unwrap();
+            survey.add_survey_response(response).unwrap();
+        }
+        7 => {
+            survey.remove_response(&respondent_id);
+        }
+        8 => {
+            survey
+                .update_response(&respondent_id, rng.rating())
+                .unwrap();
+        }
+        9 => {
+            let threshold = rng.rating();
+            survey.retain(|response| **response.score() != threshold);
+        }
+        10 => {
+            let mut shard = Survey::new();
+            for _ in 0..rng.next(4) {
+                shard
+                    .add_response(rng.next(40) as u32, rng.rating())
+                    .unwrap();
+            }
+            survey.merge(shard).unwrap();
+        }
+        _ => {
+            let respondent_ids = [respondent_id, rng.next(40) as u32];
+            survey.erase_respondents(&respondent_ids);
+        }
+    }
+}
+
+#[test]
+fn test_score_matches_fresh_recomputation_after_random_interleavings() {
+    for seed in 1..=50u64 {
+        let mut rng = Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let mut survey = Survey::new();
+
+        for _ in 0..200 {
+            apply_random_operation(&mut survey, &mut rng);
+
+            // Score only some of the time, so mutations are checked both with a warm and a cold cache.
+            if rng.next(3) == 0 {
+                let fresh = fresh_copy(&survey);
+                assert_eq!(survey.score(), fresh.score(), "seed {}", seed);
+                assert_eq!(survey.score_exact(), fresh.score_exact(), "seed {}", seed);
+                assert_eq!(survey.segment_counts(), recount(&survey), "seed {}", seed);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_score_after_from_iterator_and_extend() {
+    let responses = vec![
+        SurveyResponse::new(1, 10).map(|response| (1, response)),
+        SurveyResponse::new(2, 11).map(|response| (2, response)),
+        SurveyResponse::new(3, 3).map(|response| (3, response)),
+    ];
+    let mut survey: Survey<u32> = responses.into_iter().collect();
+    assert_eq!(survey.score(), 0);
+
+    survey.extend(vec![(4, SurveyResponse::new(4, 9).unwrap())]);
+    assert_eq!(survey.score(), 33);
+
+    survey.extend(vec![(3, SurveyResponse::new(3, 9).unwrap())]);
+    assert_eq!(survey.score(), 100);
+    assert_eq!(survey.score(), fresh_copy(&survey).score());
+}
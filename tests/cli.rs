@@ -0,0 +1,100 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const RESPONSES: &str = "\
+id,rating,region
+a,10,EMEA
+b,6,APAC
+c,9,EMEA
+d,eleven,EMEA
+e,8,APAC
+";
+
+fn nps(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_nps"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_table_output_reports_skipped_rows() {
+    let output = nps(&["--id", "id", "--score", "rating"], RESPONSES);
+    assert!(output.status.success());
+    let lines: Vec<&str> = stdout(&output).lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("responses  promoters"));
+    assert!(lines[1].contains("2 (50.0%)"));
+    assert!(lines[1].contains("25.0"));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "nps: skipped line 5: invalid score \"eleven\"\n"
+    );
+}
+
+#[test]
+fn test_grouped_csv_output() {
+    let output = nps(
+        &["-i", "id", "-s", "rating", "-g", "region", "-o", "csv"],
+        RESPONSES,
+    );
+    assert!(output.status.success());
+    let lines: Vec<&str> = stdout(&output).lines().collect();
+    assert!(lines[0].starts_with("region,responses,promoters,passives,detractors,"));
+    assert!(lines[1].starts_with("APAC,2,0,1,1,0,50,50,-50,"));
+    assert!(lines[2].starts_with("EMEA,2,2,0,0,100,0,0,100,"));
+}
+
+#[test]
+fn test_filtered_json_lines_input_and_output() {
+    let input = r#"{"respondent_id": 1, "score": 10, "channel": "web"}
+{"respondent_id": 2, "score": "2", "channel": "web"}
+{"respondent_id": 3, "score": 0, "channel": "phone"}
+"#;
+    let output = nps(&["-f", "jsonl", "-w", "channel=web", "-o", "json"], input);
+    assert!(output.status.success());
+    let summary: serde_json::Value = serde_json::from_str(stdout(&output).trim()).unwrap();
+    assert_eq!(summary["responses"], 2);
+    assert_eq!(summary["promoters"]["percent"], 50.0);
+    assert_eq!(summary["nps"], 0.0);
+    assert!(summary["confidence_interval"]["lower"].as_f64().unwrap() < 0.0);
+}
+
+#[test]
+fn test_missing_column_fails() {
+    let output = nps(&["--score", "nps"], RESPONSES);
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "nps: line 1: missing column \"respondent_id\"\n"
+    );
+}
+
+#[test]
+fn test_confidence_level() {
+    let output = nps(&["-i", "id", "-s", "rating", "-l", "0.57"], RESPONSES);
+    assert!(output.status.success());
+    assert!(stdout(&output).lines().next().unwrap().ends_with("57% ci"));
+
+    // Options are checked before any input is read.
+    let output = nps(&["-i", "id", "-s", "rating", "-l", "7"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .starts_with("nps: invalid confidence level \"7\" (expected 0 < level < 1)\n"));
+}
@@ -0,0 +1,86 @@
+//! Stress tests adding responses to a `ConcurrentSurvey` from many threads at once and checking
+//! the counts afterwards.
+use net_promoter_score::prelude::*;
+use std::thread;
+
+const THREADS: u32 = 16;
+const RESPONSES_PER_THREAD: u32 = 2_000;
+
+#[test]
+fn test_concurrent_inserts_are_all_counted() {
+    let survey = ConcurrentSurvey::new();
+    thread::scope(|scope| {
+        for thread_id in 0..THREADS {
+            let survey = &survey;
+            scope.spawn(move || {
+                for i in 0..RESPONSES_PER_THREAD {
+                    let respondent_id = thread_id * RESPONSES_PER_THREAD + i;
+                    let score = (respondent_id % 11) as NpsRating;
+                    assert_eq!(survey.add_response(respondent_id, score), Ok(None));
+                    // Reads never block and must stay within the final total.
+                    assert!(survey.len() <= (THREADS * RESPONSES_PER_THREAD) as usize);
+                }
+            });
+        }
+    });
+
+    let total = (THREADS * RESPONSES_PER_THREAD) as usize;
+    assert_eq!(survey.len(), total);
+
+    let snapshot = survey.snapshot();
+    assert_eq!(snapshot.len(), total);
+    assert_eq!(snapshot.histogram(), &survey.histogram());
+    assert_eq!(snapshot.segment_counts(), survey.segment_counts());
+    assert_eq!(snapshot.score(), survey.score());
+    for rating in 0..=10 {
+        let expected = (0..total as u32).filter(|id| id % 11 == rating).count();
+        assert_eq!(survey.histogram().count(rating as NpsRating), expected);
+    }
+}
+
+#[test]
+fn test_concurrent_duplicates_follow_policy() {
+    let mut survey = ConcurrentSurvey::new();
+    survey.set_duplicate_policy(DuplicatePolicy::KeepAll);
+    thread::scope(|scope| {
+        for thread_id in 0..THREADS {
+            let survey = &survey;
+            scope.spawn(move || {
+                // Every thread answers for the same 100 respondents.
+                for respondent_id in 0..100u32 {
+                    let score = if thread_id % 2 == 0 { 10 } else { 0 };
+                    survey.add_response(respondent_id, score).unwrap();
+                }
+            });
+        }
+    });
+
+    assert_eq!(survey.len(), 100);
+    let snapshot = survey.snapshot();
+    assert_eq!(snapshot.len(), 100);
+    assert_eq!(snapshot.segment_counts(), survey.segment_counts());
+    for respondent_id in 0..100u32 {
+        assert_eq!(snapshot.history(&respondent_id).len(), THREADS as usize - 1);
+    }
+}
+
+#[test]
+fn test_concurrent_invalid_ratings_are_rejected() {
+    let survey = ConcurrentSurvey::with_scheme(Scale::new(1, 5, 4, 5).unwrap(), 4);
+    thread::scope(|scope| {
+        for thread_id in 0..THREADS {
+            let survey = &survey;
+            scope.spawn(move || {
+                for i in 0..100u32 {
+                    let respondent_id = thread_id * 100 + i;
+                    let result = survey.add_response(respondent_id, (i % 7) as NpsRating);
+                    assert_eq!(result.is_ok(), (1..=5).contains(&(i % 7)));
+                }
+            });
+        }
+    });
+
+    let valid = (0..100u32).filter(|i| (1..=5).contains(&(i % 7))).count();
+    assert_eq!(survey.len(), THREADS as usize * valid);
+    assert_eq!(survey.snapshot().len(), survey.len());
+}